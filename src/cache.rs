@@ -6,7 +6,7 @@ use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
-use crate::{cli::Cli, platform::get_gpu_ids, sysinfo::get_gpu_name_pretty};
+use crate::{platform::get_gpu_ids, sysinfo::get_gpu_name_pretty};
 
 #[derive(Serialize, Deserialize)]
 pub struct Cache {
@@ -64,12 +64,20 @@ pub fn create_cache() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-pub fn get_cache(cli: &Cli) -> Result<Cache, Box<dyn std::error::Error>> {
+/// Reads the cache file, creating it first if it does not exist. To forcefully regenerate it (E.g:
+/// --clear-cache) call create_cache() beforehand
+pub fn get_cache() -> Result<Cache, Box<dyn std::error::Error>> {
     let cache_path = get_cache_path();
-    if std::fs::read_to_string(&cache_path).is_err() || cli.clear_cache {
+    if std::fs::read_to_string(&cache_path).is_err() {
         create_cache()?;
     }
-    let contents = std::fs::read_to_string(&cache_path)?;
+    read_cache()
+}
+
+/// Reads the cache file without ever creating it, for diagnostics that must not change anything
+/// on disk (E.g: --bug-report)
+pub fn read_cache() -> Result<Cache, Box<dyn std::error::Error>> {
+    let contents = std::fs::read_to_string(get_cache_path())?;
     let cache: Cache = toml::from_str(&contents)?;
     Ok(cache)
 }
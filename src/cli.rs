@@ -3,7 +3,12 @@
 
 use std::path::PathBuf;
 
-use clap::Parser;
+use clap::{Parser, ValueEnum, builder::PossibleValuesParser};
+
+use crate::{
+    common::LOGO_IDS,
+    config::{IconSet, ImageProtocol, LogoPosition, LogoSize, ThemePreset},
+};
 
 #[derive(Parser, Debug)]
 #[command(name = "rustfetch", version)]
@@ -31,4 +36,199 @@ pub struct Cli {
 
     #[arg(long, help = "Forcefully regenerates the cache file")]
     pub clear_cache: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = OutputFormat::Text,
+        help = "Chooses how the system info is printed"
+    )]
+    pub format: OutputFormat,
+
+    #[arg(long, conflicts_with = "format", help = "Shorthand for --format json")]
+    pub json: bool,
+
+    #[arg(
+        long,
+        value_delimiter = ',',
+        help = "Only displays the given modules, E.g: --modules ram,battery"
+    )]
+    pub modules: Vec<String>,
+
+    #[arg(
+        long,
+        value_parser = clap::value_parser!(u64).range(1..),
+        help = "Refreshes status bar formats every given amount of seconds"
+    )]
+    pub interval: Option<u64>,
+
+    #[arg(
+        long,
+        value_enum,
+        conflicts_with_all = ["format", "json"],
+        help = "Prints the logo and the info as an SVG image or an HTML page instead"
+    )]
+    pub export: Option<ExportFormat>,
+
+    #[arg(
+        long,
+        help = "Prints a Markdown summary of the system info and its sources to paste in GitHub \
+                issues"
+    )]
+    pub bug_report: bool,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["format", "json", "export"],
+        help = "Writes the logo and the info to a file such as /etc/motd instead of printing them"
+    )]
+    pub motd: Option<PathBuf>,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["format", "json", "export", "motd"],
+        help = "Checks RAM, swap, disk and battery against the [check] thresholds and exits with a \
+                Nagios-style status code"
+    )]
+    pub check: bool,
+
+    #[arg(
+        long,
+        value_name = "TEMPLATE",
+        conflicts_with_all = ["format", "json", "export", "motd", "check"],
+        help = "Prints a single line from a template, E.g: \"{os} | {cpu} | {ram.percent}%\""
+    )]
+    pub format_string: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "COLUMNS",
+        value_parser = clap::value_parser!(u16).range(1..),
+        help = "Lays the output out for the given amount of columns instead of the terminal width"
+    )]
+    pub width: Option<u16>,
+
+    #[arg(
+        long,
+        value_enum,
+        value_name = "POSITION",
+        help = "Places the logo on the left, right, top or nowhere, overriding the config"
+    )]
+    pub logo_position: Option<LogoPosition>,
+
+    #[arg(
+        long,
+        value_name = "DISTRO",
+        value_parser = PossibleValuesParser::new(LOGO_IDS),
+        help = "Uses the built-in logo and colors of another distro"
+    )]
+    pub logo: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Uses a custom logo file, - reads it from stdin"
+    )]
+    pub logo_file: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_enum,
+        value_name = "SIZE",
+        help = "Chooses a small, normal or large logo, auto picks the one closest to the info height"
+    )]
+    pub logo_size: Option<LogoSize>,
+
+    #[arg(
+        long,
+        value_name = "PNG",
+        help = "Shows a PNG as the logo, through kitty graphics, sixel or half-blocks"
+    )]
+    pub logo_image: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_enum,
+        value_name = "PROTOCOL",
+        help = "Protocol used by --logo-image, auto detects it from the terminal"
+    )]
+    pub image_protocol: Option<ImageProtocol>,
+
+    #[arg(
+        long,
+        value_name = "COLUMNS",
+        value_parser = clap::value_parser!(u16).range(1..),
+        help = "Columns taken by --logo-image, its rows follow from its aspect ratio"
+    )]
+    pub image_width: Option<u16>,
+
+    #[arg(
+        long,
+        value_enum,
+        value_name = "PRESET",
+        help = "Uses a built-in theme for the keys and values, overriding the config preset"
+    )]
+    pub theme: Option<ThemePreset>,
+
+    #[arg(
+        long,
+        value_enum,
+        value_name = "SET",
+        help = "Shows icons next to the keys, overriding the config icon set"
+    )]
+    pub icons: Option<IconSet>,
+
+    #[arg(
+        long,
+        value_enum,
+        value_name = "WHEN",
+        default_value_t = ColorChoice::Auto,
+        help = "Chooses when colors are used, auto follows the terminal, NO_COLOR and COLORTERM"
+    )]
+    pub color: ColorChoice,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Logo and colored text
+    Text,
+    /// JSON document with typed values
+    Json,
+    /// Shell variables, use with eval "$(rustfetch --format env)"
+    Env,
+    /// Gauges for node-exporter's textfile collector
+    Prometheus,
+    /// Waybar custom module JSON
+    Waybar,
+    /// i3bar protocol, refreshed every 5 seconds unless --interval is given
+    I3bar,
+    /// Polybar script module text
+    Polybar,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Colors only in terminals, unless NO_COLOR is set
+    Auto,
+    /// Colors even when piped, as many as the terminal supports
+    Always,
+    Never,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Svg,
+    Html,
+}
+
+impl Cli {
+    /// Gets the output format, taking shorthands such as --json into account
+    pub fn output_format(&self) -> OutputFormat {
+        if self.json {
+            OutputFormat::Json
+        } else {
+            self.format
+        }
+    }
 }
@@ -0,0 +1,174 @@
+//! How many colors the output can use, resolved once from --color, NO_COLOR, COLORTERM and TERM.
+//! Truecolor (E.g: logo palettes, hex colors from the config, images) is downgraded to the nearest
+//! 256 or 16 color on terminals that can not show it, such as the Linux console
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use colored::Color;
+
+use crate::cli::ColorChoice;
+
+// Levels of the 6x6x6 cube inside the xterm 256 colors, the grays come after it
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+const GRAY_START: u8 = 232;
+
+/// RGB values of the 16 terminal colors, in the same order as their ANSI codes. Terminals pick
+/// their own values, these are the ones used to find the closest color and by --export
+pub const ANSI_COLORS_RGB: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 49, 49)),
+    (Color::Green, (13, 188, 121)),
+    (Color::Yellow, (229, 229, 16)),
+    (Color::Blue, (36, 114, 200)),
+    (Color::Magenta, (188, 63, 188)),
+    (Color::Cyan, (17, 168, 205)),
+    (Color::White, (229, 229, 229)),
+    (Color::BrightBlack, (102, 102, 102)),
+    (Color::BrightRed, (241, 76, 76)),
+    (Color::BrightGreen, (35, 209, 139)),
+    (Color::BrightYellow, (245, 245, 67)),
+    (Color::BrightBlue, (59, 142, 234)),
+    (Color::BrightMagenta, (214, 112, 214)),
+    (Color::BrightCyan, (41, 184, 219)),
+    (Color::BrightWhite, (255, 255, 255)),
+];
+
+// Read by fit_color(), truecolor until main.rs resolves the terminal
+static COLOR_DEPTH: AtomicU8 = AtomicU8::new(ColorDepth::TrueColor as u8);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorDepth {
+    None,
+    Ansi16,
+    Ansi256,
+    TrueColor,
+}
+
+/// Sets the depth used by fit_color(), whether colors are shown at all is still up to
+/// colored::control::set_override()
+pub fn set_color_depth(depth: ColorDepth) {
+    COLOR_DEPTH.store(depth as u8, Ordering::Relaxed);
+}
+
+pub fn get_color_depth() -> ColorDepth {
+    match COLOR_DEPTH.load(Ordering::Relaxed) {
+        0 => ColorDepth::None,
+        1 => ColorDepth::Ansi16,
+        2 => ColorDepth::Ansi256,
+        _ => ColorDepth::TrueColor,
+    }
+}
+
+/// Finds out how many colors to use. With auto colors are only used in terminals, NO_COLOR turns
+/// them off and CLICOLOR_FORCE turns them on even when piped.
+/// The depth comes from COLORTERM ("truecolor" or "24bit") and TERM (E.g: "xterm-256color"),
+/// anything else gets the 16 colors every terminal has
+pub fn resolve_color_depth(
+    choice: ColorChoice,
+    is_terminal: bool,
+    env: impl Fn(&str) -> Option<String>,
+) -> ColorDepth {
+    let term = env("TERM").unwrap_or_default();
+    let no_color = env("NO_COLOR").is_some_and(|value| !value.is_empty());
+    let force = env("CLICOLOR_FORCE").is_some_and(|value| !value.is_empty() && value != "0");
+    let disabled = env("CLICOLOR").is_some_and(|value| value == "0");
+
+    let enabled = match choice {
+        ColorChoice::Never => false,
+        ColorChoice::Always => true,
+        ColorChoice::Auto => force || (is_terminal && !no_color && !disabled && term != "dumb"),
+    };
+    if !enabled {
+        return ColorDepth::None;
+    }
+
+    let colorterm = env("COLORTERM").unwrap_or_default();
+    if colorterm == "truecolor" || colorterm == "24bit" {
+        ColorDepth::TrueColor
+    } else if term.contains("256color") {
+        ColorDepth::Ansi256
+    } else {
+        ColorDepth::Ansi16
+    }
+}
+
+/// Downgrades a color to the depth set by set_color_depth(), named colors are shown by every
+/// terminal so they are left as they are
+pub fn fit_color(color: Color) -> Color {
+    downgrade_color(color, get_color_depth())
+}
+
+pub fn downgrade_color(color: Color, depth: ColorDepth) -> Color {
+    match (color, depth) {
+        (Color::TrueColor { r, g, b }, ColorDepth::Ansi256) => {
+            Color::AnsiColor(get_closest_256_color(r, g, b))
+        },
+        (Color::TrueColor { r, g, b }, ColorDepth::Ansi16) => get_closest_16_color(r, g, b),
+        // E.g: palettes and theme colors that are already one of the 256 colors
+        (Color::AnsiColor(index), ColorDepth::Ansi16) => match ANSI_COLORS_RGB.get(index as usize) {
+            Some((named, _)) => *named,
+            None => {
+                let (r, g, b) = get_256_color_rgb(index);
+                get_closest_16_color(r, g, b)
+            },
+        },
+        _ => color,
+    }
+}
+
+/// Gets the RGB value of one of the xterm 256 colors
+pub fn get_256_color_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0 ..= 15 => ANSI_COLORS_RGB[index as usize].1,
+        16 ..= 231 => {
+            let cube_index = index - 16;
+            (
+                CUBE_LEVELS[(cube_index / 36) as usize],
+                CUBE_LEVELS[(cube_index / 6 % 6) as usize],
+                CUBE_LEVELS[(cube_index % 6) as usize],
+            )
+        },
+        _ => {
+            let gray = 8 + (index - GRAY_START) * 10;
+            (gray, gray, gray)
+        },
+    }
+}
+
+/// Gets the index of the closest xterm 256 color, either inside the 6x6x6 cube or among the 24
+/// grays, whichever is closer
+pub fn get_closest_256_color(r: u8, g: u8, b: u8) -> u8 {
+    let closest_level = |value: u8| {
+        (0 .. CUBE_LEVELS.len())
+            .min_by_key(|&index| CUBE_LEVELS[index].abs_diff(value))
+            .unwrap_or(0)
+    };
+    let (red, green, blue) = (closest_level(r), closest_level(g), closest_level(b));
+    let cube = (CUBE_LEVELS[red], CUBE_LEVELS[green], CUBE_LEVELS[blue]);
+    let cube_index = 16 + 36 * red + 6 * green + blue;
+
+    // Grays go from 8 to 238 in steps of 10
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let gray_index = (average.saturating_sub(3) / 10).min(23) as u8;
+    let gray = 8 + 10 * gray_index;
+
+    if get_distance((r, g, b), (gray, gray, gray)) < get_distance((r, g, b), cube) {
+        GRAY_START + gray_index
+    } else {
+        cube_index as u8
+    }
+}
+
+/// Gets the closest of the 16 terminal colors
+pub fn get_closest_16_color(r: u8, g: u8, b: u8) -> Color {
+    ANSI_COLORS_RGB
+        .iter()
+        .min_by_key(|(_, rgb)| get_distance((r, g, b), *rgb))
+        .map(|(color, _)| *color)
+        .unwrap_or(Color::White)
+}
+
+fn get_distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let channel = |x: u8, y: u8| (x.abs_diff(y) as u32).pow(2);
+    channel(a.0, b.0) + channel(a.1, b.1) + channel(a.2, b.2)
+}
@@ -1,160 +1,285 @@
 use colored::*;
-use sysinfo::System;
 
 use crate::{
-    cli::Cli,
-    common::{extract_numeric_value, round_to_two_decimal},
-    config::Config,
-    platform::{self, get_power_draw},
-    sysinfo::*,
+    common::{
+        PercentageThresholds, Theme, convert_to_bytes, fit_color, format_uptime, get_display_width,
+        round_to_two_decimal,
+    },
+    config::{ColorStyle, ColorsConfig, ProgressBarMode},
+    report::*,
 };
 
-fn color_percentage(percentage: u64) -> ColoredString {
-    if percentage < 40 {
-        format!("{}%", percentage).green()
-    } else if (40 .. 80).contains(&percentage) {
-        format!("{}%", percentage).yellow()
-    } else {
-        format!("{}%", percentage).red()
-    }
+const KIB_IN_BYTES: u64 = 1024;
+pub const BYTES_IN_GB: u64 = 1_000_000_000;
+
+// The 16 terminal colors in the order of their ANSI codes, their exact look depends on the theme
+// of the terminal which is the point of the colors module
+const ANSI_COLORS: [Color; 8] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::White,
+];
+const BRIGHT_ANSI_COLORS: [Color; 8] = [
+    Color::BrightBlack,
+    Color::BrightRed,
+    Color::BrightGreen,
+    Color::BrightYellow,
+    Color::BrightBlue,
+    Color::BrightMagenta,
+    Color::BrightCyan,
+    Color::BrightWhite,
+];
+
+/// How worrying a percentage is, shared by every output that highlights percentages (colors in the
+/// terminal, CSS classes in status bars exc)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PercentageLevel {
+    Ok,
+    Warn,
+    Critical,
 }
 
-fn color_percentage_inverse(percentage: f64) -> ColoredString {
-    if percentage < 30.0 {
-        format!("{}%", percentage).red()
-    } else if (30.0 .. 70.0).contains(&percentage) {
-        format!("{}%", percentage).yellow()
-    } else {
-        format!("{}%", percentage).green()
+impl PercentageLevel {
+    /// Gets the level of a usage percentage (RAM, disk exc) with the built-in thresholds, where
+    /// higher is worse
+    pub fn from_usage(percentage: u64) -> Self {
+        PercentageThresholds::usage().get_level(percentage as f64)
+    }
+
+    /// Gets the level of a capacity percentage (battery) with the built-in thresholds, where lower
+    /// is worse
+    pub fn from_capacity(percentage: f64) -> Self {
+        PercentageThresholds::capacity().get_level(percentage)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Warn => "warn",
+            Self::Critical => "critical",
+        }
     }
 }
 
-pub fn display_os() -> String {
-    format!(
-        "{} {} ({})",
-        "OS:".bold(),
-        get_os_name(),
-        std::env::consts::ARCH // CPU Architecture the program was compiled for
-    )
+/// Gets the color of a percentage according to the thresholds of its module, E.g: green for 20% of
+/// RAM with the built-in thresholds
+fn get_percentage_color(percentage: u64, thresholds: &PercentageThresholds) -> Color {
+    fit_color(thresholds.get_color(percentage as f64))
 }
 
-pub fn display_kernel() -> String {
-    format!("{} {}", "Kernel:".bold(), platform::format_kernel_version())
+fn color_percentage(percentage: u64, thresholds: &PercentageThresholds) -> ColoredString {
+    format!("{}%", percentage).color(get_percentage_color(percentage, thresholds))
 }
 
-pub fn display_cpu(sys: &System, config: &Config) -> String {
-    let cpu_name = get_cpu_name(sys);
+/// Shows the used and total amounts of a module followed by its percentage, with a progress bar
+/// in between or in place of the amounts if [progress_bars] asks for it
+fn format_usage(
+    module: &str,
+    amounts: String,
+    percentage: u64,
+    thresholds: &PercentageThresholds,
+    theme: &Theme,
+) -> String {
+    let colored_percentage = color_percentage(percentage, thresholds);
+    let bar_color = get_percentage_color(percentage, thresholds);
+    let bar = || theme.progress_bars.render(percentage, bar_color);
 
-    let cpu_frequency;
-    if config.display.cpu_frequency {
-        let frequency = get_cpu_frequency(sys);
-        if frequency >= 1000 {
-            cpu_frequency = format!(" @ {} GHz ", round_to_two_decimal(frequency as f64 / 1000.0))
-        } else {
-            cpu_frequency = format!(" @ {} MHz ", frequency)
-        }
-    } else {
-        cpu_frequency = String::from("");
+    match theme.progress_bars.get_mode(module) {
+        ProgressBarMode::None => format!("{} ({})", amounts, colored_percentage),
+        ProgressBarMode::Both => format!("{} {} {}", amounts, bar(), colored_percentage),
+        ProgressBarMode::Bar => format!("{} {}", bar(), colored_percentage),
     }
+}
 
-    format!("{} {}{}", "CPU:".bold(), cpu_name, cpu_frequency)
+pub fn display_os(os: &OsInfo, theme: &Theme) -> String {
+    theme.format_line(&theme.icons.label("os", "OS"), &format!("{} ({})", os.name, os.arch))
 }
 
-pub fn display_ram_usage(sys: &System) -> String {
-    let (total, used, percentage) = get_ram_usage(sys);
-    format!("{} {} / {} ({})", "RAM:".bold(), used, total, color_percentage(percentage))
+pub fn display_kernel(kernel: &KernelInfo, theme: &Theme) -> String {
+    theme.format_line(
+        &theme.icons.label("kernel", "Kernel"),
+        &format!("{} {}", kernel.name, kernel.version),
+    )
 }
 
-pub fn display_swap_usage(sys: &System) -> String {
-    let (total, used, percentage) = get_swap_usage(sys);
-    if extract_numeric_value(&total).is_ok_and(|v| v == 0.0) {
-        format!("{} Disabled", "Swap:".bold())
+pub fn display_cpu(cpu: &CpuInfo, theme: &Theme) -> String {
+    let cpu_frequency;
+    if let Some(frequency) = cpu.frequency_mhz {
+        cpu_frequency = format!(" @ {} ", format_frequency(frequency))
     } else {
-        format!("{} {} / {} ({})", "Swap:".bold(), used, total, color_percentage(percentage))
+        cpu_frequency = String::from("");
     }
+
+    theme.format_line(&theme.icons.label("cpu", "CPU"), &format!("{}{}", cpu.name, cpu_frequency))
 }
 
-pub fn display_uptime() -> String {
-    format!("{} {}", "Uptime:".bold(), get_uptime())
+pub fn display_ram_usage(ram: &MemoryInfo, theme: &Theme) -> String {
+    let amounts = format!("{} / {}", format_memory(ram.used_bytes), format_memory(ram.total_bytes));
+    theme.format_line(
+        &theme.icons.label("ram", "RAM"),
+        &format_usage("ram", amounts, ram.percent, &theme.thresholds.ram, theme),
+    )
 }
 
-pub fn display_battery() -> Option<String> {
-    let (capacity, status) = platform::get_battery();
-    if capacity != "Unavailable" && status != "Unavailable" {
-        Some(format!(
-            "{} {} ({})",
-            "Battery:".bold(),
-            color_percentage_inverse(capacity.parse::<f64>().unwrap_or(0.0)),
-            status
-        ))
+pub fn display_swap_usage(swap: &MemoryInfo, theme: &Theme) -> String {
+    if swap.total_bytes == 0 {
+        theme.format_line(&theme.icons.label("swap", "Swap"), "Disabled")
     } else {
-        None
+        let amounts =
+            format!("{} / {}", format_memory(swap.used_bytes), format_memory(swap.total_bytes));
+        theme.format_line(
+            &theme.icons.label("swap", "Swap"),
+            &format_usage("swap", amounts, swap.percent, &theme.thresholds.swap, theme),
+        )
     }
 }
-pub fn display_power_draw() -> Option<String> {
-    let power_draw = get_power_draw();
-    if power_draw != 0 {
-        Some(format!("{} {}W", "Power Draw:".bold(), power_draw))
-    } else {
-        None
-    }
+
+pub fn display_uptime(uptime: &UptimeInfo, theme: &Theme) -> String {
+    theme.format_line(&theme.icons.label("uptime", "Uptime"), &format_uptime(uptime.seconds))
+}
+
+pub fn display_battery(battery: &BatteryInfo, theme: &Theme) -> String {
+    let capacity = battery.capacity_percent;
+    let percentage = color_percentage(capacity, &theme.thresholds.battery);
+    let bar_color = get_percentage_color(capacity, &theme.thresholds.battery);
+    let bar = || theme.progress_bars.render(capacity, bar_color);
+
+    let value = match theme.progress_bars.get_mode("battery") {
+        ProgressBarMode::None => format!("{} ({})", percentage, battery.status),
+        ProgressBarMode::Both => format!("{} {} ({})", bar(), percentage, battery.status),
+        ProgressBarMode::Bar => format!("{} {}", bar(), percentage),
+    };
+    theme.format_line(&theme.icons.battery_label(capacity, &battery.status, "Battery"), &value)
 }
 
-pub fn display_disk_usage() -> String {
-    let (total, used, percentage) = platform::get_disk_usage();
-    format!(
-        "{} {}GB / {}GB ({})",
-        "Disk (/):".bold(), // FIXME: Shows "/" dir statically
-        used,
-        total,
-        color_percentage(percentage)
+pub fn display_power_draw(power_draw: &PowerDrawInfo, theme: &Theme) -> String {
+    theme.format_line(
+        &theme.icons.label("power_draw", "Power Draw"),
+        &format!("{}W", power_draw.watts as u32),
     )
 }
 
-pub fn display_gpu_name(cli: &Cli) -> Option<String> {
-    platform::get_gpu_name(cli).map(|gpu_name| format!("{} {}", "GPU:".bold(), gpu_name))
+pub fn display_disk_usage(disk: &DiskInfo, theme: &Theme) -> String {
+    theme.format_line(
+        &theme.icons.label("disk", &format!("Disk ({})", disk.mount_point)),
+        &format_usage(
+            "disk",
+            format!("{}GB / {}GB", disk.used_bytes / BYTES_IN_GB, disk.total_bytes / BYTES_IN_GB),
+            disk.percent,
+            &theme.thresholds.disk,
+            theme,
+        ),
+    )
 }
 
-pub fn display_screen(config: &Config) -> Option<String> {
-    if !config.display.resolution && !config.display.refresh_rate {
-        // I'm sure theres a better way to do this, but this works as well
-        return None;
-    }
+pub fn display_gpu_name(gpu: &GpuInfo, theme: &Theme) -> String {
+    theme.format_line(&theme.icons.label("gpu", "GPU"), &gpu.name)
+}
 
+pub fn display_screen(screen: &ScreenInfo, theme: &Theme) -> String {
     let resolution;
-    if config.display.resolution
-        && let Some((width, height)) = get_screen_resolution()
-    {
+    if let Some(Resolution { width, height }) = screen.resolution {
         resolution = format!("{}x{}", width, height);
     } else {
         resolution = String::from("");
     }
 
     let refresh_rate;
-    if config.display.refresh_rate
-        && let Some(rr) = get_screen_refresh_rate()
-    {
+    if let Some(rr) = screen.refresh_rate_hz {
         refresh_rate = format!("@ {}Hz", rr);
     } else {
         refresh_rate = String::from("");
     }
 
-    Some(format!("{} {} {}", "Screen:".bold(), resolution, refresh_rate))
+    theme.format_line(
+        &theme.icons.label("screen", "Screen"),
+        &format!("{} {}", resolution, refresh_rate),
+    )
 }
 
-pub fn display_identifier() -> Vec<String> {
-    let mut host_name = String::from("");
-    if let Some(host) = get_host_name() {
-        host_name = host;
-    }
-    let username = get_username();
+/// Shows user@host with the key looks of the theme, underlined by the underline of the theme unless
+/// it is empty
+pub fn display_identifier(identifier: &IdentifierInfo, theme: &Theme) -> Vec<String> {
+    let host_name = identifier.host_name.as_deref().unwrap_or("");
+    let identifier = format!("{}@{}", identifier.username, host_name);
+
+    // Wide underlines (E.g: "═" in some fonts, or emojis) are repeated less
+    let underline_width = get_display_width(&theme.underline);
+    let Some(count) = get_display_width(&identifier).checked_div(underline_width) else {
+        return vec![theme.paint_key(&identifier)];
+    };
+
+    // The underline is measured before painting, bolding everything up messes with the width
+    vec![theme.paint_key(&identifier), theme.underline.repeat(count)]
+}
+
+/// Shows the terminal colors as a row of swatches, plus a row of bright colors if enabled
+pub fn display_colors(colors: &ColorsConfig) -> Vec<String> {
+    let rows: &[[Color; 8]] = if colors.bright {
+        &[ANSI_COLORS, BRIGHT_ANSI_COLORS]
+    } else {
+        &[ANSI_COLORS]
+    };
 
-    let mut identifier = format!("{}@{}", username, host_name);
-    let underline = "-".repeat(identifier.chars().count());
+    rows.iter()
+        .map(|row| match colors.style {
+            ColorStyle::Blocks => {
+                row.iter().map(|&color| "   ".on_color(color).to_string()).collect()
+            },
+            ColorStyle::Circles => join_swatches(row, "●"),
+            ColorStyle::Custom => join_swatches(row, &colors.glyph),
+        })
+        .collect()
+}
 
-    // this may add overhead and i will try to find a better way,
-    // though bolding everything up messes with the underline
-    identifier = format!("{}", identifier.bold());
+fn join_swatches(row: &[Color; 8], glyph: &str) -> String {
+    row.iter().map(|&color| glyph.color(color).to_string()).collect::<Vec<String>>().join(" ")
+}
 
-    vec![identifier, underline]
+/// Renders every module inside the report as a colored line, in the order they're shown next to the
+/// logo. Modules that are None are skipped
+pub fn get_info_lines(report: &SystemReport, theme: &Theme) -> Vec<String> {
+    report
+        .identifier
+        .as_ref()
+        .map(|identifier| display_identifier(identifier, theme))
+        .into_iter()
+        .flatten()
+        .chain(
+            vec![
+                report.os.as_ref().map(|os| display_os(os, theme)),
+                report.kernel.as_ref().map(|kernel| display_kernel(kernel, theme)),
+                report.cpu.as_ref().map(|cpu| display_cpu(cpu, theme)),
+                report.gpu.as_ref().map(|gpu| display_gpu_name(gpu, theme)),
+                report.screen.as_ref().map(|screen| display_screen(screen, theme)),
+                report.ram.as_ref().map(|ram| display_ram_usage(ram, theme)),
+                report.swap.as_ref().map(|swap| display_swap_usage(swap, theme)),
+                report.uptime.as_ref().map(|uptime| display_uptime(uptime, theme)),
+                report.battery.as_ref().map(|battery| display_battery(battery, theme)),
+                report.power_draw.as_ref().map(|power_draw| display_power_draw(power_draw, theme)),
+                report.disk.as_ref().map(|disk| display_disk_usage(disk, theme)),
+            ]
+            .into_iter()
+            .flatten(),
+        )
+        .collect()
+}
+
+/// Memory values are shown in KiB, MB or GB depending on their size
+pub fn format_memory(bytes: u64) -> String {
+    convert_to_bytes((bytes / KIB_IN_BYTES) as f64).unwrap_or(String::from("0 KiB"))
+}
+
+/// CPU frequencies are shown in GHz, or in MHz below 1 GHz. E.g: 800 is "800 MHz"
+pub fn format_frequency(frequency_mhz: u64) -> String {
+    if frequency_mhz >= 1000 {
+        format!("{} GHz", round_to_two_decimal(frequency_mhz as f64 / 1000.0))
+    } else {
+        format!("{} MHz", frequency_mhz)
+    }
 }
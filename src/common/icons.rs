@@ -0,0 +1,154 @@
+//! Icons shown before (or instead of) the key of every module. They come from a built-in set
+//! (--icons or set inside [icons]) and every icon set inside [icons] overrides the built-in one
+
+use crate::config::{IconMode, IconSet, IconsConfig};
+
+// Nerd Font battery icons from empty to full, one every 10%
+const NERD_BATTERY_ICONS: [&str; 11] = [
+    "\u{f008e}",
+    "\u{f007a}",
+    "\u{f007b}",
+    "\u{f007c}",
+    "\u{f007d}",
+    "\u{f007e}",
+    "\u{f007f}",
+    "\u{f0080}",
+    "\u{f0081}",
+    "\u{f0082}",
+    "\u{f0079}",
+];
+const NERD_BATTERY_CHARGING_ICON: &str = "\u{f0084}";
+
+// Below this the emoji battery looks empty
+const LOW_BATTERY_PERCENTAGE: u64 = 20;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Icons {
+    pub set: IconSet,
+    pub mode: IconMode,
+    /// Custom icons by module name, E.g: ("cpu", "🧠")
+    pub custom: Vec<(String, String)>,
+}
+
+impl Icons {
+    /// Builds the icons from the config, the set comes from --icons if given and from the config
+    /// otherwise
+    pub fn new(icons_config: &IconsConfig, set: Option<IconSet>) -> Self {
+        let custom = [
+            ("os", &icons_config.os),
+            ("kernel", &icons_config.kernel),
+            ("uptime", &icons_config.uptime),
+            ("cpu", &icons_config.cpu),
+            ("gpu", &icons_config.gpu),
+            ("screen", &icons_config.screen),
+            ("ram", &icons_config.ram),
+            ("swap", &icons_config.swap),
+            ("disk", &icons_config.disk),
+            ("battery", &icons_config.battery),
+            ("power_draw", &icons_config.power_draw),
+        ]
+        .into_iter()
+        .filter_map(|(module, icon)| icon.as_ref().map(|icon| (module.to_string(), icon.clone())))
+        .collect();
+
+        Self {
+            set: set.unwrap_or(icons_config.set),
+            mode: icons_config.mode,
+            custom,
+        }
+    }
+
+    /// Gets the icon of a module, custom icons take precedence over the built-in set
+    pub fn get_icon(&self, module: &str) -> Option<&str> {
+        self.get_custom_icon(module).or_else(|| get_builtin_icon(self.set, module))
+    }
+
+    /// Gets the battery icon, which follows the capacity and whether it is charging unless a custom
+    /// one is set
+    pub fn get_battery_icon(&self, capacity_percent: u64, status: &str) -> Option<&str> {
+        self.get_custom_icon("battery")
+            .or_else(|| get_builtin_battery_icon(self.set, capacity_percent, status))
+    }
+
+    /// Puts the icon of a module next to its key, or in its place. Keys without an icon are left as
+    /// they are.
+    ///
+    /// For example: "cpu" and "CPU" become "🧠 CPU" with the emoji set
+    pub fn label(&self, module: &str, key: &str) -> String {
+        self.apply(self.get_icon(module), key)
+    }
+
+    pub fn battery_label(&self, capacity_percent: u64, status: &str, key: &str) -> String {
+        self.apply(self.get_battery_icon(capacity_percent, status), key)
+    }
+
+    fn get_custom_icon(&self, module: &str) -> Option<&str> {
+        self.custom.iter().find(|(name, _)| name == module).map(|(_, icon)| icon.as_str())
+    }
+
+    fn apply(&self, icon: Option<&str>, key: &str) -> String {
+        match (icon, self.mode) {
+            (Some(icon), IconMode::Prefix) => format!("{} {}", icon, key),
+            (Some(icon), IconMode::Replace) => icon.to_string(),
+            (None, _) => key.to_string(),
+        }
+    }
+}
+
+/// Gets the icon of a module inside a built-in set. Emojis are picked among the ones that are
+/// always two columns wide, emojis that need a variation selector (E.g: "⚙️") are not measured
+/// the same way by every terminal and would break the alignment
+pub fn get_builtin_icon(set: IconSet, module: &str) -> Option<&'static str> {
+    let icon = match set {
+        IconSet::None => return None,
+        IconSet::Nerd => match module {
+            "os" => "\u{f17c}",
+            "kernel" => "\u{f013}",
+            "uptime" => "\u{f017}",
+            "cpu" => "\u{f2db}",
+            "gpu" => "\u{f08ae}",
+            "screen" => "\u{f0379}",
+            "ram" => "\u{f035b}",
+            "swap" => "\u{f04e1}",
+            "disk" => "\u{f02ca}",
+            "battery" => NERD_BATTERY_ICONS[10],
+            "power_draw" => "\u{f140b}",
+            _ => return None,
+        },
+        IconSet::Emoji => match module {
+            "os" => "🐧",
+            "kernel" => "🌰",
+            "uptime" => "⏰",
+            "cpu" => "🧠",
+            "gpu" => "🎮",
+            "screen" => "📺",
+            "ram" => "💾",
+            "swap" => "🔁",
+            "disk" => "💽",
+            "battery" => "🔋",
+            "power_draw" => "⚡",
+            _ => return None,
+        },
+    };
+    Some(icon)
+}
+
+/// Gets the battery icon of a built-in set. Statuses come from platform::get_battery(), E.g:
+/// "Charging", "Discharging", "Full"
+pub fn get_builtin_battery_icon(
+    set: IconSet,
+    capacity_percent: u64,
+    status: &str,
+) -> Option<&'static str> {
+    let charging = status.eq_ignore_ascii_case("charging");
+
+    let icon = match set {
+        IconSet::None => return None,
+        IconSet::Nerd if charging => NERD_BATTERY_CHARGING_ICON,
+        IconSet::Nerd => NERD_BATTERY_ICONS[(capacity_percent.min(100) / 10) as usize],
+        IconSet::Emoji if charging => "🔌",
+        IconSet::Emoji if capacity_percent < LOW_BATTERY_PERCENTAGE => "🪫",
+        IconSet::Emoji => "🔋",
+    };
+    Some(icon)
+}
@@ -0,0 +1,400 @@
+//! Image logos through --logo-image, drawn with the kitty graphics protocol or sixel. The image
+//! takes the place of the logo column: write_image_logo() draws it first and then lays the info out
+//! next to blank logo lines of the same size, so the alignment is the same as with text logos.
+//! Terminals without either protocol get the image as half-block text, see render_half_blocks()
+
+use std::{
+    io::{BufWriter, Cursor, Write},
+    path::Path,
+};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use colored::*;
+
+use crate::{
+    common::{
+        Layout, fit_color, fit_info_lines, get_display_width, get_side_info_width, is_logo_shown,
+        write_logo,
+    },
+    config::{ImageProtocol, LogoPosition},
+};
+
+/// Size of a terminal cell in pixels. Terminals scale kitty images into the cells by themselves,
+/// but sixel draws pixels so it relies on these. Most terminals with a 12-14pt font are close
+pub const CELL_WIDTH: usize = 10;
+pub const CELL_HEIGHT: usize = 20;
+
+/// Kitty wants the payload split into chunks of at most 4096 bytes
+const KITTY_CHUNK_SIZE: usize = 4096;
+
+/// A decoded PNG, pixels are RGBA and go row by row. data keeps the original file since kitty can
+/// display PNGs as they are
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PngImage {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<[u8; 4]>,
+    pub data: Vec<u8>,
+}
+
+/// An image ready to be drawn, columns and rows are the cells it covers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageLogo {
+    pub columns: usize,
+    pub rows: usize,
+    pub escape: String,
+}
+
+impl ImageLogo {
+    /// Auto must be resolved through resolve_image_protocol() beforehand and is treated as Kitty.
+    /// Half-blocks are a text logo instead, so they get no escape sequence
+    pub fn new(image: &PngImage, protocol: ImageProtocol, columns: usize) -> Self {
+        let columns = columns.max(1);
+        let rows = get_image_rows(image.width, image.height, columns);
+
+        let escape = match protocol {
+            ImageProtocol::Auto | ImageProtocol::Kitty => encode_kitty(&image.data, columns, rows),
+            ImageProtocol::Sixel => encode_sixel(image, columns, rows),
+            ImageProtocol::HalfBlocks => String::new(),
+        };
+
+        Self {
+            columns,
+            rows,
+            escape,
+        }
+    }
+}
+
+/// Reads and decodes a PNG file
+///
+/// # Errors
+/// Returns an error if the file cannot be read or is not a valid PNG
+pub fn load_png(path: &Path) -> Result<PngImage, String> {
+    let data = std::fs::read(path)
+        .map_err(|e| format!("Could not read logo image {}: {}", path.display(), e))?;
+    decode_png(data).map_err(|e| format!("Could not decode logo image {}: {}", path.display(), e))
+}
+
+/// Decodes a PNG into RGBA pixels, whatever its color type and bit depth
+///
+/// # Errors
+/// Returns an error if the data is not a valid PNG
+pub fn decode_png(data: Vec<u8>) -> Result<PngImage, String> {
+    let mut decoder = png::Decoder::new(Cursor::new(&data));
+    // Expands palettes and bit depths other than 8, so only 4 color types are left
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(|e| e.to_string())?;
+
+    let buffer_size = reader.output_buffer_size().ok_or("The image is too large")?;
+    let mut buffer = vec![0; buffer_size];
+    let info = reader.next_frame(&mut buffer).map_err(|e| e.to_string())?;
+    let bytes = &buffer[.. info.buffer_size()];
+
+    let pixels = match info.color_type {
+        png::ColorType::Rgba => bytes.chunks_exact(4).map(|p| [p[0], p[1], p[2], p[3]]).collect(),
+        png::ColorType::Rgb => bytes.chunks_exact(3).map(|p| [p[0], p[1], p[2], 255]).collect(),
+        png::ColorType::GrayscaleAlpha => {
+            bytes.chunks_exact(2).map(|p| [p[0], p[0], p[0], p[1]]).collect()
+        },
+        png::ColorType::Grayscale => bytes.iter().map(|&g| [g, g, g, 255]).collect(),
+        png::ColorType::Indexed => return Err(String::from("Unsupported color type")),
+    };
+
+    Ok(PngImage {
+        width: info.width as usize,
+        height: info.height as usize,
+        pixels,
+        data,
+    })
+}
+
+/// Picks kitty or sixel from the environment variables the terminal sets, terminals that do not
+/// support images (or are not known to) get half-blocks. Other protocols are returned as they are.
+///
+/// env is usually |key| std::env::var(key).ok(), it is a parameter so tests can fake a terminal
+pub fn resolve_image_protocol(
+    protocol: ImageProtocol,
+    env: impl Fn(&str) -> Option<String>,
+) -> ImageProtocol {
+    if protocol != ImageProtocol::Auto {
+        return protocol;
+    }
+
+    let term = env("TERM").unwrap_or_default();
+    let term_program = env("TERM_PROGRAM").unwrap_or_default();
+
+    if env("KITTY_WINDOW_ID").is_some()
+        || matches!(term.as_str(), "xterm-kitty" | "xterm-ghostty")
+        || matches!(term_program.as_str(), "WezTerm" | "ghostty")
+    {
+        ImageProtocol::Kitty
+    } else if term.contains("sixel")
+        || term.starts_with("foot")
+        || term.starts_with("contour")
+        || env("MLTERM").is_some()
+    {
+        ImageProtocol::Sixel
+    } else {
+        ImageProtocol::HalfBlocks
+    }
+}
+
+/// Gets the rows an image takes when scaled to the given columns, keeping its aspect ratio.
+///
+/// For example: a 100x100 image on 30 columns is 300 pixels wide, so 15 rows tall
+pub fn get_image_rows(width: usize, height: usize, columns: usize) -> usize {
+    if width == 0 {
+        return 1;
+    }
+    (columns * CELL_WIDTH * height).div_ceil(width * CELL_HEIGHT).max(1)
+}
+
+/// Builds the kitty graphics escape sequence that displays a PNG over the given cells. The cursor
+/// stays where it is (C=1) and replies from the terminal are silenced (q=2)
+pub fn encode_kitty(data: &[u8], columns: usize, rows: usize) -> String {
+    let payload = STANDARD.encode(data);
+    let chunks: Vec<&[u8]> = payload.as_bytes().chunks(KITTY_CHUNK_SIZE).collect();
+    let mut escape = String::new();
+
+    for (i, chunk) in chunks.iter().enumerate() {
+        // m=1 tells the terminal that more chunks follow
+        let more = usize::from(i + 1 < chunks.len());
+        // base64 is always ASCII
+        let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+
+        if i == 0 {
+            escape.push_str(&format!(
+                "\x1b_Ga=T,f=100,q=2,C=1,c={},r={},m={};{}\x1b\\",
+                columns, rows, more, chunk
+            ));
+        } else {
+            escape.push_str(&format!("\x1b_Gm={};{}\x1b\\", more, chunk));
+        }
+    }
+
+    escape
+}
+
+/// Builds the sixel escape sequence of an image scaled to the given cells. Colors are reduced to
+/// a 6x6x6 cube (216 colors) since sixel uses a palette, transparent pixels are left untouched
+pub fn encode_sixel(image: &PngImage, columns: usize, rows: usize) -> String {
+    let width = columns * CELL_WIDTH;
+    let height = (image.height * width)
+        .checked_div(image.width)
+        .unwrap_or(0)
+        .min(rows * CELL_HEIGHT);
+
+    // None is a transparent pixel
+    let grid: Vec<Option<usize>> =
+        scale_image(image, width, height).into_iter().map(get_cube_index).collect();
+
+    // P2=1 keeps transparent pixels transparent, the raster attributes set the size
+    let mut escape = format!("\x1bP0;1;0q\"1;1;{};{}", width, height);
+
+    let mut used = [false; 216];
+    grid.iter().flatten().for_each(|&index| used[index] = true);
+    for index in (0 .. 216).filter(|&index| used[index]) {
+        // Registers take percentages, every level of the cube is 20%
+        let (r, g, b) = (index / 36, index / 6 % 6, index % 6);
+        escape.push_str(&format!("#{};2;{};{};{}", index, r * 20, g * 20, b * 20));
+    }
+
+    // Every sixel character covers a column of 6 pixels, so the image is drawn in bands of 6 rows
+    for band in (0 .. height).step_by(6) {
+        let band_rows = band .. (band + 6).min(height);
+        let mut band_colors: Vec<usize> = band_rows
+            .clone()
+            .flat_map(|y| grid[y * width .. (y + 1) * width].iter().flatten().copied())
+            .collect();
+        band_colors.sort_unstable();
+        band_colors.dedup();
+
+        for color in band_colors {
+            let sixels: Vec<u8> = (0 .. width)
+                .map(|x| {
+                    let bits = band_rows
+                        .clone()
+                        .enumerate()
+                        .filter(|(_, y)| grid[y * width + x] == Some(color))
+                        .fold(0, |bits, (bit, _)| bits | 1 << bit);
+                    63 + bits
+                })
+                .collect();
+
+            escape.push_str(&format!("#{}", color));
+            push_sixel_runs(&mut escape, &sixels);
+            // $ goes back to the start of the band for the next color
+            escape.push('$');
+        }
+        escape.push('-');
+    }
+
+    escape.push_str("\x1b\\");
+    escape
+}
+
+/// Scales an image to the given size in pixels. Every pixel is the average of the pixels it
+/// covers, colors are weighted by their alpha so that transparent pixels do not darken the edges
+pub fn scale_image(image: &PngImage, width: usize, height: usize) -> Vec<[u8; 4]> {
+    // The pixels of the source image a pixel of the scaled one covers, at least one
+    let span = |i: usize, source: usize, target: usize| {
+        let start = i * source / target;
+        start .. ((i + 1) * source / target).max(start + 1)
+    };
+
+    (0 .. width * height)
+        .map(|i| {
+            let (x, y) = (i % width, i / width);
+            let mut sum = [0u64; 4];
+            let mut count = 0;
+
+            for source_y in span(y, image.height, height) {
+                for source_x in span(x, image.width, width) {
+                    let Some(pixel) = image.pixels.get(source_y * image.width + source_x) else {
+                        continue;
+                    };
+                    let alpha = pixel[3] as u64;
+                    (0 .. 3).for_each(|c| sum[c] += pixel[c] as u64 * alpha);
+                    sum[3] += alpha;
+                    count += 1;
+                }
+            }
+
+            match sum[3] {
+                0 => [0, 0, 0, 0],
+                alpha => [
+                    (sum[0] / alpha) as u8,
+                    (sum[1] / alpha) as u8,
+                    (sum[2] / alpha) as u8,
+                    (alpha / count) as u8,
+                ],
+            }
+        })
+        .collect()
+}
+
+/// Renders an image as truecolor text, every character is two pixels: the upper half block (▀)
+/// takes the color of the top one and its background the color of the bottom one. Since cells are
+/// twice as tall as they are wide, pixels come out square.
+///
+/// The lines go through write_logo() like any other logo, so they work in every terminal
+pub fn render_half_blocks(image: &PngImage, columns: usize) -> Vec<String> {
+    let columns = columns.max(1);
+    let Some(height) = (image.height * columns).checked_div(image.width) else {
+        return Vec::new();
+    };
+    let height = height.max(1);
+    let pixels = scale_image(image, columns, height);
+    let visible = |pixel: &&[u8; 4]| pixel[3] >= 128;
+
+    (0 .. height)
+        .step_by(2)
+        .map(|y| {
+            (0 .. columns)
+                .map(|x| {
+                    let top = pixels.get(y * columns + x).filter(visible);
+                    let bottom = pixels.get((y + 1) * columns + x).filter(|_| y + 1 < height);
+                    match (top, bottom.filter(visible)) {
+                        (Some(top), Some(bottom)) => {
+                            "▀".color(get_pixel_color(top)).on_color(get_pixel_color(bottom))
+                        },
+                        (Some(top), None) => "▀".color(get_pixel_color(top)),
+                        (None, Some(bottom)) => "▄".color(get_pixel_color(bottom)),
+                        (None, None) => " ".normal(),
+                    }
+                    .to_string()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Gets the color of a pixel as the terminal can show it, see fit_color()
+fn get_pixel_color(pixel: &[u8; 4]) -> Color {
+    fit_color(Color::TrueColor {
+        r: pixel[0],
+        g: pixel[1],
+        b: pixel[2],
+    })
+}
+
+/// Gets the index of the closest color inside the 6x6x6 cube, None for transparent pixels
+fn get_cube_index(pixel: [u8; 4]) -> Option<usize> {
+    if pixel[3] < 128 {
+        return None;
+    }
+    let level = |value: u8| (value as usize * 5 + 127) / 255;
+    Some(level(pixel[0]) * 36 + level(pixel[1]) * 6 + level(pixel[2]))
+}
+
+/// Writes sixel characters with run-length encoding (E.g: !10~ is ten ~), empty sixels at the end
+/// of the band are skipped
+fn push_sixel_runs(escape: &mut String, sixels: &[u8]) {
+    let end = sixels.iter().rposition(|&sixel| sixel != b'?').map_or(0, |i| i + 1);
+    let mut i = 0;
+
+    while i < end {
+        let sixel = sixels[i];
+        let count = sixels[i .. end].iter().take_while(|&&s| s == sixel).count();
+        if count > 3 {
+            escape.push_str(&format!("!{}{}", count, sixel as char));
+        } else {
+            (0 .. count).for_each(|_| escape.push(sixel as char));
+        }
+        i += count;
+    }
+}
+
+pub fn print_image_logo(
+    image: &ImageLogo,
+    info_lines: Vec<String>,
+    layout: &Layout,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let stdout = std::io::stdout();
+    let mut handle = BufWriter::new(stdout.lock());
+
+    write_image_logo(&mut handle, image, info_lines, layout)?;
+
+    handle.flush()?;
+    Ok(())
+}
+
+/// Draws the image where the logo column goes and then writes the info through write_logo(), with
+/// blank logo lines as large as the image
+pub fn write_image_logo(
+    handle: &mut impl Write,
+    image: &ImageLogo,
+    info_lines: Vec<String>,
+    layout: &Layout,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if is_logo_shown(image.columns, layout) {
+        // On the right, the image goes after the widest info line, the padding and two spaces
+        let column = match layout.logo_position {
+            LogoPosition::Right => {
+                let info_width = get_side_info_width(image.columns, layout);
+                fit_info_lines(info_lines.clone(), info_width, layout)
+                    .iter()
+                    .map(|line| get_display_width(line))
+                    .max()
+                    .unwrap_or(0)
+                    + layout.padding
+                    + 2
+            },
+            _ => 0,
+        };
+
+        // Scrolls the terminal beforehand if the image does not fit below the cursor, otherwise the
+        // saved cursor position would be off once the image scrolls it
+        write!(handle, "{}\x1b[{}A", "\n".repeat(image.rows), image.rows)?;
+        // Saves the cursor, moves to the logo column, draws the image and goes back
+        write!(handle, "\x1b7")?;
+        if column > 0 {
+            write!(handle, "\x1b[{}C", column)?;
+        }
+        write!(handle, "{}\x1b8", image.escape)?;
+    }
+
+    let blank_lines = vec![" ".repeat(image.columns); image.rows];
+    write_logo(handle, blank_lines, info_lines, "", layout)
+}
@@ -1,15 +1,71 @@
 //! This file handles everything related to displaying the distro logo
 
-use std::io::{BufWriter, Write};
+use std::{
+    io::{BufWriter, Write},
+    path::Path,
+};
 
 use colored::*;
 
-use crate::cli::Cli;
+use crate::{
+    cli::Cli,
+    common::{fit_color, get_display_width, strip_ansi, truncate_to_width, wrap_to_width},
+    config::{LayoutConfig, LogoPosition, LogoSize, Overflow},
+};
 
-/// Gets the lines logos in a vector and returns them
-pub fn get_logo_lines(distro_id: &str) -> Vec<String> {
+/// Every distro id that has its own logo inside ../ascii
+pub const LOGO_IDS: &[&str] = &[
+    "arch",
+    "ubuntu",
+    "fedora",
+    "manjaro",
+    "debian",
+    "opensuse",
+    "alpine",
+    "gentoo",
+    "endeavouros",
+    "popos",
+    "cachyos",
+    "garuda",
+    "linuxmint",
+    "kali",
+    "macos",
+    "zorin",
+    "elementary",
+    "nixos",
+    "linux",
+];
+
+/// Logo used when neither the distro nor the ones it is based on have a logo
+pub const FALLBACK_LOGO_ID: &str = "linux";
+
+/// Walks the distro id and then the ids it is based on (ID_LIKE), returning the first one that has
+/// a logo. Falls back to the generic Tux logo.
+///
+/// For example: Kubuntu has ID=kubuntu and ID_LIKE="ubuntu debian", so it gets the Ubuntu logo
+pub fn resolve_logo_id(distro_id: &str, like_ids: &[String]) -> String {
+    std::iter::once(distro_id)
+        .chain(like_ids.iter().map(String::as_str))
+        .find(|id| LOGO_IDS.contains(id))
+        .unwrap_or(FALLBACK_LOGO_ID)
+        .to_string()
+}
+
+/// Gets the lines of a logo in the given size. Distros that do not ship the size get their normal
+/// logo, Auto must be resolved through choose_logo_size() beforehand and is treated as Normal
+pub fn get_logo_lines(distro_id: &str, size: LogoSize) -> Vec<String> {
+    let variant = match size {
+        LogoSize::Small => get_small_logo(distro_id),
+        LogoSize::Large => get_large_logo(distro_id),
+        LogoSize::Normal | LogoSize::Auto => None,
+    };
+
+    variant.unwrap_or_else(|| get_normal_logo(distro_id)).lines().map(|l| l.to_string()).collect()
+}
+
+fn get_normal_logo(distro_id: &str) -> &'static str {
     // include_str!() works even in compiled binaries
-    let logo = match distro_id {
+    match distro_id {
         "arch" => include_str!("../../ascii/arch.txt"),
         "ubuntu" => include_str!("../../ascii/ubuntu.txt"),
         "fedora" => include_str!("../../ascii/fedora.txt"),
@@ -28,36 +84,237 @@ pub fn get_logo_lines(distro_id: &str) -> Vec<String> {
         "zorin" => include_str!("../../ascii/zorin.txt"),
         "elementary" => include_str!("../../ascii/elementary.txt"),
         "nixos" => include_str!("../../ascii/nixos.txt"),
+        "linux" => include_str!("../../ascii/linux.txt"),
         _ => "",
+    }
+}
+
+fn get_small_logo(distro_id: &str) -> Option<&'static str> {
+    match distro_id {
+        "ubuntu" => Some(include_str!("../../ascii/small/ubuntu.txt")),
+        "elementary" => Some(include_str!("../../ascii/small/elementary.txt")),
+        _ => None,
+    }
+}
+
+fn get_large_logo(distro_id: &str) -> Option<&'static str> {
+    match distro_id {
+        "arch" => Some(include_str!("../../ascii/large/arch.txt")),
+        "debian" => Some(include_str!("../../ascii/large/debian.txt")),
+        "fedora" => Some(include_str!("../../ascii/large/fedora.txt")),
+        "ubuntu" => Some(include_str!("../../ascii/large/ubuntu.txt")),
+        "linux" => Some(include_str!("../../ascii/large/linux.txt")),
+        _ => None,
+    }
+}
+
+/// Resolves Auto into the size whose logo is the closest in height to the info, among the sizes
+/// the distro ships. Sizes taller than the terminal are skipped, if the terminal height is known.
+/// Other sizes are returned as they are.
+///
+/// For example: 12 info lines pick the 12 lines tall large Tux over the 7 lines tall normal one
+pub fn choose_logo_size(
+    distro_id: &str,
+    size: LogoSize,
+    info_line_count: usize,
+    terminal_height: Option<usize>,
+) -> LogoSize {
+    if size != LogoSize::Auto {
+        return size;
+    }
+
+    let normal_height = get_normal_logo(distro_id).lines().count();
+    // Normal comes first so that it wins ties
+    let candidates = [
+        Some((LogoSize::Normal, normal_height)),
+        get_small_logo(distro_id).map(|logo| (LogoSize::Small, logo.lines().count())),
+        get_large_logo(distro_id).map(|logo| (LogoSize::Large, logo.lines().count())),
+    ];
+    // The prompt takes up a line after the output
+    let fits = |height: usize| terminal_height.is_none_or(|terminal| height < terminal);
+
+    let chosen = candidates
+        .into_iter()
+        .flatten()
+        .filter(|(_, height)| fits(*height))
+        .min_by_key(|(_, height)| height.abs_diff(info_line_count))
+        .map(|(size, _)| size);
+
+    // If nothing fits, the smallest logo is the one that breaks the layout the least
+    chosen.unwrap_or(if get_small_logo(distro_id).is_some() {
+        LogoSize::Small
+    } else {
+        LogoSize::Normal
+    })
+}
+
+/// Reads a custom logo, "-" reads it from stdin instead. Color markers work the same way as in the
+/// built-in logos
+///
+/// # Errors
+/// Returns an error if the file or stdin cannot be read
+pub fn read_logo_file(path: &Path) -> Result<Vec<String>, String> {
+    let content = if path == Path::new("-") {
+        std::io::read_to_string(std::io::stdin())
+            .map_err(|e| format!("Could not read the logo from stdin: {}", e))?
+    } else {
+        std::fs::read_to_string(path)
+            .map_err(|e| format!("Could not read logo file {}: {}", path.display(), e))?
     };
 
-    logo.lines().map(|l| l.to_string()).collect()
+    Ok(content.lines().map(|l| l.to_string()).collect())
+}
+
+/// A piece of a logo line that shares the same color. color_index points into the palette of the
+/// distro and starts from 0, so $1 is color_index 0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogoSegment {
+    pub text: String,
+    pub color_index: usize,
 }
 
-/// Getting a reference to an element of the vector of strings we're printing and matching it with
-/// the distro id, we get return its colorized version.
-/// Example: if distro_id = ubuntu, then we return line.orange()
-pub fn colorize_logo_line(distro_id: &str, line: &str) -> ColoredString {
+/// Gets the colors used by the $1, $2 ... markers of a logo, in order. Logos without markers are
+/// painted with the first color
+pub fn get_logo_palette(distro_id: &str) -> Vec<Color> {
+    // The exact colors should be tested on your distro and eventually changed it they do not match
+    // the color of the distro's logo excessively
     match distro_id {
-        // The exact colors should be tested on your distro and eventually changed it they do not
-        // match the color of the distro's logo excessively
-        "arch" => line.truecolor(23, 147, 209),
-        "ubuntu" => line.truecolor(255, 156, 0),
-        "cachyos" => line.truecolor(0, 184, 148),
-        "fedora" => line.truecolor(11, 87, 164),
-        "garuda" => line.truecolor(138, 43, 226),
-        "gentoo" => line.truecolor(84, 73, 149),
-        "endeavouros" => line.truecolor(122, 58, 237),
-        "kali" => line.truecolor(38, 139, 210),
-        "linuxmint" | "manjaro" => line.green(),
-        "debian" => line.red(),
-        "alpine" => line.cyan(),
-        "popos" => line.truecolor(72, 149, 239),
-        "opensuse" => line.truecolor(115, 186, 37),
-        "nixos" => line.truecolor(125, 176, 221),
-        "zorin" => line.truecolor(17, 162, 236),
-        "elementary" => line.white(),
-        _ => line.white(), // this includes macos
+        "arch" => vec![rgb(23, 147, 209)],
+        "ubuntu" => vec![rgb(255, 156, 0), Color::White],
+        "cachyos" => vec![rgb(0, 184, 148)],
+        "fedora" => vec![rgb(11, 87, 164)],
+        "garuda" => vec![rgb(138, 43, 226)],
+        "gentoo" => vec![rgb(84, 73, 149), Color::White],
+        "endeavouros" => vec![rgb(231, 76, 60), rgb(122, 58, 237), rgb(52, 152, 219)],
+        "kali" => vec![rgb(38, 139, 210)],
+        "linuxmint" => vec![Color::Green, Color::White],
+        "manjaro" => vec![Color::Green],
+        "debian" => vec![Color::Red],
+        "alpine" => vec![Color::Cyan],
+        "popos" => vec![rgb(72, 149, 239)],
+        "opensuse" => vec![rgb(115, 186, 37)],
+        "nixos" => vec![rgb(125, 176, 221)],
+        "zorin" => vec![rgb(17, 162, 236)],
+        "linux" => vec![Color::White, Color::Yellow],
+        _ => vec![Color::White], // this includes macos and elementary
+    }
+}
+
+fn rgb(r: u8, g: u8, b: u8) -> Color {
+    Color::TrueColor { r, g, b }
+}
+
+/// Splits every logo line into colored segments. Like in neofetch, a color marker lasts until the
+/// next one, even across lines
+pub fn parse_logo(logo_lines: &[String]) -> Vec<Vec<LogoSegment>> {
+    let mut color_index = 0;
+    logo_lines.iter().map(|line| parse_logo_line(line, &mut color_index)).collect()
+}
+
+/// Splits a line on its $1 to $9 markers, color_index is the color the line starts with and is
+/// left at the last color used. A $ that is not followed by a digit is kept as is.
+///
+/// For example: "$1//$2oss" becomes "//" with color 0 and "oss" with color 1
+pub fn parse_logo_line(line: &str, color_index: &mut usize) -> Vec<LogoSegment> {
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let marker = match chars.peek() {
+            Some(digit @ '1' ..= '9') if c == '$' => digit.to_digit(10),
+            _ => None,
+        };
+        let Some(marker) = marker else {
+            text.push(c);
+            continue;
+        };
+        chars.next();
+
+        if !text.is_empty() {
+            segments.push(LogoSegment {
+                text: std::mem::take(&mut text),
+                color_index: *color_index,
+            });
+        }
+        *color_index = marker as usize - 1;
+    }
+
+    if !text.is_empty() {
+        segments.push(LogoSegment {
+            text,
+            color_index: *color_index,
+        });
+    }
+    segments
+}
+
+/// Gets the width in terminal columns of a parsed logo line, markers are not part of it since they
+/// are never printed
+pub fn get_logo_line_width(segments: &[LogoSegment]) -> usize {
+    segments.iter().map(|segment| get_display_width(&segment.text)).sum()
+}
+
+/// Paints every segment with its palette color, markers beyond the palette use the first color
+pub fn colorize_logo_line(segments: &[LogoSegment], palette: &[Color]) -> String {
+    segments
+        .iter()
+        .map(|segment| {
+            let color = palette
+                .get(segment.color_index)
+                .or(palette.first())
+                .copied()
+                .unwrap_or(Color::White);
+            segment.text.color(fit_color(color)).to_string()
+        })
+        .collect()
+}
+
+/// Everything that decides where the logo and the info go, resolved from the CLI and the config
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layout {
+    /// Spaces between the logo and the info, or empty lines if the logo is on top
+    pub padding: usize,
+    /// Columns available, None means unlimited (E.g: the output is piped or written to a file) so
+    /// values are always shown in full
+    pub width: Option<usize>,
+    pub overflow: Overflow,
+    /// Below this width only the info is shown, unless the logo is on top
+    pub logo_min_width: usize,
+    pub logo_position: LogoPosition,
+    /// Separator between keys and values of the theme, wrapped values continue after it
+    pub separator: String,
+}
+
+impl Layout {
+    pub fn new(
+        cli: &Cli,
+        layout_config: &LayoutConfig,
+        width: Option<usize>,
+        separator: &str,
+    ) -> Self {
+        Self {
+            padding: cli.padding as usize,
+            width,
+            overflow: layout_config.overflow,
+            logo_min_width: layout_config.logo_min_width,
+            // The CLI flag takes precedence over the config file
+            logo_position: cli.logo_position.unwrap_or(layout_config.logo_position),
+            separator: separator.to_string(),
+        }
+    }
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self {
+            padding: 1,
+            width: None,
+            overflow: Overflow::Truncate,
+            logo_min_width: 0,
+            logo_position: LogoPosition::Left,
+            separator: String::from(": "),
+        }
     }
 }
 
@@ -65,41 +322,149 @@ pub fn print_logo(
     logo_lines: Vec<String>,
     info_lines: Vec<String>,
     distro_id: &str,
-    cli: &Cli,
+    layout: &Layout,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let stdout = std::io::stdout();
     let mut handle = BufWriter::new(stdout.lock());
 
-    if logo_lines.is_empty() {
-        // If the logo does not match any inside ../ascii/LOGO.txt, just print the info
-        for line in info_lines {
-            let _ = writeln!(handle, "{}", line);
+    write_logo(&mut handle, logo_lines, info_lines, distro_id, layout)?;
+
+    handle.flush()?;
+    Ok(())
+}
+
+/// Writes the logo and the info lines into any writer according to the logo position, this is what
+/// print_logo() prints to stdout and what gets exported by --export
+pub fn write_logo(
+    handle: &mut impl Write,
+    logo_lines: Vec<String>,
+    info_lines: Vec<String>,
+    distro_id: &str,
+    layout: &Layout,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let logo = parse_logo(&logo_lines);
+    let palette = get_logo_palette(distro_id);
+    // We get the maximum length from the logo using .max(), markers are already stripped
+    let logo_column_width = logo.iter().map(|l| get_logo_line_width(l)).max().unwrap_or(0);
+
+    let show_logo = !logo.is_empty() && is_logo_shown(logo_column_width, layout);
+
+    if !show_logo {
+        // If the logo does not match any inside ../ascii/LOGO.txt, is turned off or the terminal is
+        // too narrow, just print the info
+        for line in fit_info_lines(info_lines, layout.width, layout) {
+            writeln!(handle, "{}", line)?;
         }
-    } else {
-        let max_lines = logo_lines.len().max(info_lines.len());
-        // We get the maximum length from the logo using .max()
-        let logo_column_width = logo_lines.iter().map(|l| l.len()).max().unwrap_or(0);
+        return Ok(());
+    }
+
+    let logo: Vec<(String, usize)> = logo
+        .iter()
+        .map(|line| (colorize_logo_line(line, &palette), get_logo_line_width(line)))
+        .collect();
+
+    if layout.logo_position == LogoPosition::Top {
+        for (line, _) in &logo {
+            writeln!(handle, "{}", line)?;
+        }
+        // Padding is vertical when the logo is on top
+        for _ in 0 .. layout.padding {
+            writeln!(handle)?;
+        }
+        for line in fit_info_lines(info_lines, layout.width, layout) {
+            writeln!(handle, "{}", line)?;
+        }
+        return Ok(());
+    }
+
+    let info_width = get_side_info_width(logo_column_width, layout);
+    let info_lines = fit_info_lines(info_lines, info_width, layout);
+    let max_lines = logo.len().max(info_lines.len());
+
+    if layout.logo_position == LogoPosition::Right {
+        let info_column_width =
+            info_lines.iter().map(|line| get_display_width(line)).max().unwrap_or(0);
 
         for i in 0 .. max_lines {
-            if i < logo_lines.len() {
-                write!(handle, "{}", colorize_logo_line(distro_id, &logo_lines[i]))?;
-                let padding =
-                    logo_column_width.saturating_sub(logo_lines[i].len()) + cli.padding as usize;
-                write!(handle, "{:width$}", "", width = padding)?;
-            } else {
-                // when past logo lines, print spaces that are logo_column_width + padding
-                let total_width = logo_column_width + cli.padding as usize;
-                write!(handle, "{:width$}", "", width = total_width)?;
-            }
-
-            if i < info_lines.len() {
-                writeln!(handle, "  {}", info_lines[i])?;
-            } else {
-                writeln!(handle)?;
-            }
+            let info_line = info_lines.get(i).map(String::as_str).unwrap_or("");
+            let Some((logo_line, _)) = logo.get(i) else {
+                writeln!(handle, "{}", info_line)?;
+                continue;
+            };
+
+            let padding = info_column_width.saturating_sub(get_display_width(info_line))
+                + layout.padding
+                + 2;
+            writeln!(handle, "{}{:width$}{}", info_line, "", logo_line, width = padding)?;
+        }
+        return Ok(());
+    }
+
+    for i in 0 .. max_lines {
+        if let Some((logo_line, logo_line_width)) = logo.get(i) {
+            write!(handle, "{}", logo_line)?;
+            let padding = logo_column_width.saturating_sub(*logo_line_width) + layout.padding;
+            write!(handle, "{:width$}", "", width = padding)?;
+        } else {
+            // when past logo lines, print spaces that are logo_column_width + padding
+            let total_width = logo_column_width + layout.padding;
+            write!(handle, "{:width$}", "", width = total_width)?;
+        }
+
+        if i < info_lines.len() {
+            writeln!(handle, "  {}", info_lines[i])?;
+        } else {
+            writeln!(handle)?;
         }
     }
 
-    handle.flush()?;
     Ok(())
 }
+
+/// Whether a logo as wide as logo_column_width is shown with this layout
+pub fn is_logo_shown(logo_column_width: usize, layout: &Layout) -> bool {
+    // A logo on top only needs to fit by itself, while a logo on the side shares the width
+    let min_width = match layout.logo_position {
+        LogoPosition::Top => logo_column_width,
+        _ => layout.logo_min_width,
+    };
+    layout.logo_position != LogoPosition::None
+        && layout.width.is_none_or(|width| width >= min_width)
+}
+
+/// Gets the width left to the info when it is next to the logo, which takes the logo column, the
+/// padding and two spaces
+pub fn get_side_info_width(logo_column_width: usize, layout: &Layout) -> Option<usize> {
+    layout.width.map(|width| width.saturating_sub(logo_column_width + layout.padding + 2))
+}
+
+/// Makes every info line fit into the given width by truncating or wrapping it according to the
+/// layout, wrapped values continue under themselves instead of under their key. Lines are left as
+/// they are without a width
+pub fn fit_info_lines(
+    info_lines: Vec<String>,
+    width: Option<usize>,
+    layout: &Layout,
+) -> Vec<String> {
+    let Some(width) = width else {
+        return info_lines;
+    };
+
+    match layout.overflow {
+        Overflow::Truncate => {
+            info_lines.iter().map(|line| truncate_to_width(line, width)).collect()
+        },
+        Overflow::Wrap => info_lines
+            .iter()
+            .flat_map(|line| {
+                // E.g: "CPU: " is 5 columns wide, lines without a key are wrapped under themselves
+                let plain = strip_ansi(line);
+                let indent = plain
+                    .find(&layout.separator)
+                    .map(|i| get_display_width(&plain[.. i + layout.separator.len()]))
+                    .unwrap_or(0);
+                wrap_to_width(line, width, indent)
+            })
+            .collect(),
+    }
+}
@@ -6,3 +6,21 @@ pub use display::*;
 
 mod logo;
 pub use logo::*;
+
+mod image;
+pub use image::*;
+
+mod theme;
+pub use theme::*;
+
+mod icons;
+pub use icons::*;
+
+mod progress_bar;
+pub use progress_bar::*;
+
+mod thresholds;
+pub use thresholds::*;
+
+mod color_mode;
+pub use color_mode::*;
@@ -0,0 +1,73 @@
+//! Progress bars shared by every module with a percentage (RAM, swap, disk and battery), E.g:
+//! "[██████░░░░]". The percentage next to the bar is left to display.rs
+
+use colored::*;
+
+use crate::{
+    common::{fit_color, parse_color},
+    config::{ProgressBarMode, ProgressBarsConfig},
+};
+
+/// Modules that have a percentage, the only ones that can have a bar
+pub const PROGRESS_BAR_MODULES: [&str; 4] = ["ram", "swap", "disk", "battery"];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProgressBars {
+    pub mode: ProgressBarMode,
+    pub modules: Vec<String>,
+    pub width: usize,
+    pub filled: String,
+    pub empty: String,
+    /// Overrides the color of the percentage
+    pub filled_color: Option<Color>,
+    pub empty_color: Option<Color>,
+}
+
+impl ProgressBars {
+    /// # Errors
+    /// Returns an error on unknown colors and on modules without a percentage
+    pub fn new(config: &ProgressBarsConfig) -> Result<Self, String> {
+        if let Some(module) =
+            config.modules.iter().find(|module| !PROGRESS_BAR_MODULES.contains(&module.as_str()))
+        {
+            return Err(format!("Module without a percentage in progress_bars: {}", module));
+        }
+
+        Ok(Self {
+            mode: config.mode,
+            modules: config.modules.clone(),
+            width: config.width,
+            filled: config.filled.clone(),
+            empty: config.empty.clone(),
+            filled_color: config.filled_color.as_deref().map(parse_color).transpose()?,
+            empty_color: config.empty_color.as_deref().map(parse_color).transpose()?,
+        })
+    }
+
+    /// Gets how a module shows its percentage, modules that are not listed never get a bar
+    pub fn get_mode(&self, module: &str) -> ProgressBarMode {
+        if self.modules.iter().any(|name| name == module) {
+            self.mode
+        } else {
+            ProgressBarMode::None
+        }
+    }
+
+    /// Renders the bar of a percentage, the filled part gets the given color (the one of the
+    /// percentage) unless filled_color is set. Percentages above 100 fill the whole bar.
+    ///
+    /// For example: 61 becomes "[██████░░░░]" with the default width of 10
+    pub fn render(&self, percentage: u64, color: Color) -> String {
+        let filled_count = (percentage.min(100) as usize * self.width + 50) / 100;
+
+        let filled_color = fit_color(self.filled_color.unwrap_or(color));
+        let filled = self.filled.repeat(filled_count).color(filled_color);
+        let empty = self.empty.repeat(self.width - filled_count);
+        let empty = match self.empty_color {
+            Some(empty_color) => empty.color(fit_color(empty_color)),
+            None => empty.normal(),
+        };
+
+        format!("[{}{}]", filled, empty)
+    }
+}
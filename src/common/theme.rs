@@ -0,0 +1,159 @@
+//! Looks of the info lines: how keys, values, the separator between them and the identifier
+//! underline are painted. Themes start from a preset (--theme or preset inside [theme]) and every
+//! option set inside [theme] overrides the one of the preset
+
+use colored::*;
+
+use crate::{
+    common::{Icons, ProgressBars, Thresholds, fit_color, parse_color},
+    config::{ThemeConfig, ThemePreset},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    pub key_color: Option<Color>,
+    pub key_styles: Vec<Styles>,
+    pub value_color: Option<Color>,
+    pub separator: String,
+    pub underline: String,
+    /// Resolved into key_color by follow_logo(), since the logo is only known later on
+    pub keys_follow_logo: bool,
+    /// Not part of the presets, set from [icons] and --icons
+    pub icons: Icons,
+    /// Not part of the presets, set from [progress_bars]
+    pub progress_bars: ProgressBars,
+    /// Not part of the presets, set from [thresholds]
+    pub thresholds: Thresholds,
+}
+
+impl Theme {
+    pub fn from_preset(preset: ThemePreset) -> Self {
+        let theme = Self {
+            key_color: None,
+            key_styles: vec![Styles::Bold],
+            value_color: None,
+            separator: String::from(": "),
+            underline: String::from("-"),
+            keys_follow_logo: false,
+            icons: Icons::default(),
+            progress_bars: ProgressBars::default(),
+            thresholds: Thresholds::default(),
+        };
+
+        match preset {
+            ThemePreset::Default => theme,
+            ThemePreset::Distro => Self {
+                keys_follow_logo: true,
+                ..theme
+            },
+            ThemePreset::Minimal => Self {
+                key_styles: vec![Styles::Dimmed],
+                separator: String::from(" "),
+                underline: String::new(),
+                ..theme
+            },
+            ThemePreset::Arrows => Self {
+                separator: String::from(" ➜ "),
+                underline: String::from("─"),
+                keys_follow_logo: true,
+                ..theme
+            },
+        }
+    }
+
+    /// Builds the theme from the config, preset comes from --theme if given and from the config
+    /// otherwise
+    ///
+    /// # Errors
+    /// Returns an error on unknown colors and styles
+    pub fn new(theme_config: &ThemeConfig, preset: Option<ThemePreset>) -> Result<Self, String> {
+        let mut theme = Self::from_preset(preset.unwrap_or(theme_config.preset));
+
+        if let Some(color) = &theme_config.key_color {
+            theme.key_color = Some(parse_color(color)?);
+        }
+        if let Some(styles) = &theme_config.key_style {
+            theme.key_styles =
+                styles.iter().map(|style| parse_style(style)).collect::<Result<_, String>>()?;
+        }
+        if let Some(color) = &theme_config.value_color {
+            theme.value_color = Some(parse_color(color)?);
+        }
+        if let Some(separator) = &theme_config.separator {
+            theme.separator = separator.clone();
+        }
+        if let Some(underline) = &theme_config.underline {
+            theme.underline = underline.clone();
+        }
+        if let Some(keys_follow_logo) = theme_config.keys_follow_logo {
+            theme.keys_follow_logo = keys_follow_logo;
+        }
+
+        Ok(theme)
+    }
+
+    /// Paints the keys with the given logo color (E.g: the first color of the distro palette) if
+    /// the theme asks for it
+    pub fn follow_logo(&mut self, logo_color: Option<Color>) {
+        if self.keys_follow_logo && logo_color.is_some() {
+            self.key_color = logo_color;
+        }
+    }
+
+    /// Paints a key with the key color and styles, E.g: "CPU" or "user@host"
+    pub fn paint_key(&self, key: &str) -> String {
+        let mut key = match self.key_color {
+            Some(color) => key.color(fit_color(color)),
+            None => key.normal(),
+        };
+        for style in &self.key_styles {
+            key = match style {
+                Styles::Bold => key.bold(),
+                Styles::Dimmed => key.dimmed(),
+                Styles::Italic => key.italic(),
+                Styles::Underline => key.underline(),
+                _ => key,
+            };
+        }
+        key.to_string()
+    }
+
+    /// Paints a value with the value color, parts of it that are already colored (E.g:
+    /// percentages) keep their own color
+    pub fn paint_value(&self, value: &str) -> String {
+        match self.value_color {
+            Some(color) => value.color(fit_color(color)).to_string(),
+            None => value.to_string(),
+        }
+    }
+
+    /// Builds an info line out of a key and its value. The separator is painted like the key,
+    /// except for its trailing spaces.
+    ///
+    /// For example: "CPU" and "AMD Ryzen 5 5600X" become "CPU: AMD Ryzen 5 5600X" by default
+    pub fn format_line(&self, key: &str, value: &str) -> String {
+        let (mark, spaces) = self.separator.split_at(self.separator.trim_end().len());
+        format!(
+            "{}{}{}",
+            self.paint_key(&format!("{}{}", key, mark)),
+            spaces,
+            self.paint_value(value)
+        )
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::from_preset(ThemePreset::Default)
+    }
+}
+
+fn parse_style(style: &str) -> Result<Styles, String> {
+    match style.trim().to_lowercase().as_str() {
+        "bold" => Ok(Styles::Bold),
+        "dimmed" => Ok(Styles::Dimmed),
+        "italic" => Ok(Styles::Italic),
+        "underline" => Ok(Styles::Underline),
+        _ => Err(format!("Unknown key style: {}", style)),
+    }
+}
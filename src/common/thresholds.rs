@@ -0,0 +1,163 @@
+//! Where the percentages of RAM, swap, disk and battery change color. Every module has a list of
+//! levels, each one starting at its percentage and going on until the next one. Built-in levels
+//! can be replaced per module inside [thresholds]
+
+use colored::Color;
+
+use crate::{
+    common::{PercentageLevel, parse_color},
+    config::{ThresholdLevelConfig, ThresholdsConfig},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThresholdLevel {
+    /// First percentage of the level
+    pub at: u64,
+    pub color: Color,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PercentageThresholds {
+    /// Never empty and sorted by the percentage they start at
+    pub levels: Vec<ThresholdLevel>,
+    /// Lower percentages are worse (E.g: battery), so the first level is the critical one instead
+    /// of the last one
+    pub inverse: bool,
+}
+
+impl PercentageThresholds {
+    /// Thresholds of usage percentages (RAM, disk exc), where higher is worse
+    pub fn usage() -> Self {
+        Self {
+            levels: vec![
+                ThresholdLevel {
+                    at: 0,
+                    color: Color::Green,
+                },
+                ThresholdLevel {
+                    at: 40,
+                    color: Color::Yellow,
+                },
+                ThresholdLevel {
+                    at: 80,
+                    color: Color::Red,
+                },
+            ],
+            inverse: false,
+        }
+    }
+
+    /// Thresholds of capacity percentages (battery), where lower is worse
+    pub fn capacity() -> Self {
+        Self {
+            levels: vec![
+                ThresholdLevel {
+                    at: 0,
+                    color: Color::Red,
+                },
+                ThresholdLevel {
+                    at: 30,
+                    color: Color::Yellow,
+                },
+                ThresholdLevel {
+                    at: 70,
+                    color: Color::Green,
+                },
+            ],
+            inverse: true,
+        }
+    }
+
+    /// Replaces the levels with the ones set in the config, if any
+    ///
+    /// # Errors
+    /// Returns an error on unknown colors, on an empty list and on levels that do not go up,
+    /// E.g: 80 followed by 40
+    pub fn with_config(
+        mut self,
+        module: &str,
+        config: Option<&[ThresholdLevelConfig]>,
+    ) -> Result<Self, String> {
+        let Some(config) = config else {
+            return Ok(self);
+        };
+        if config.is_empty() {
+            return Err(format!("Thresholds of {} need at least one level", module));
+        }
+        if let Some(pair) = config.windows(2).find(|pair| pair[0].at >= pair[1].at) {
+            return Err(format!(
+                "Thresholds of {} must go up: {} then {}",
+                module, pair[0].at, pair[1].at
+            ));
+        }
+
+        self.levels = config
+            .iter()
+            .map(|level| {
+                Ok(ThresholdLevel {
+                    at: level.at,
+                    color: parse_color(&level.color)?,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(self)
+    }
+
+    /// Gets the index of the level a percentage falls in, percentages below the first level
+    /// belong to it
+    fn get_index(&self, percentage: f64) -> usize {
+        self.levels.iter().rposition(|level| percentage >= level.at as f64).unwrap_or(0)
+    }
+
+    /// Gets how worrying a percentage is from the position of its level: the best level is ok,
+    /// the worst one is critical and everything in between is warn
+    pub fn get_level(&self, percentage: f64) -> PercentageLevel {
+        let last = self.levels.len().saturating_sub(1);
+        let index = self.get_index(percentage);
+        let rank = if self.inverse { last - index } else { index };
+
+        match rank {
+            0 => PercentageLevel::Ok,
+            rank if rank == last => PercentageLevel::Critical,
+            _ => PercentageLevel::Warn,
+        }
+    }
+
+    pub fn get_color(&self, percentage: f64) -> Color {
+        self.levels.get(self.get_index(percentage)).map_or(Color::White, |level| level.color)
+    }
+}
+
+/// Thresholds of every module with a percentage
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Thresholds {
+    pub ram: PercentageThresholds,
+    pub swap: PercentageThresholds,
+    pub disk: PercentageThresholds,
+    pub battery: PercentageThresholds,
+}
+
+impl Thresholds {
+    /// # Errors
+    /// Returns an error if the thresholds of any module are invalid
+    pub fn new(config: &ThresholdsConfig) -> Result<Self, String> {
+        Ok(Self {
+            ram: PercentageThresholds::usage().with_config("ram", config.ram.as_deref())?,
+            swap: PercentageThresholds::usage().with_config("swap", config.swap.as_deref())?,
+            disk: PercentageThresholds::usage().with_config("disk", config.disk.as_deref())?,
+            battery: PercentageThresholds::capacity()
+                .with_config("battery", config.battery.as_deref())?,
+        })
+    }
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            ram: PercentageThresholds::usage(),
+            swap: PercentageThresholds::usage(),
+            disk: PercentageThresholds::usage(),
+            battery: PercentageThresholds::capacity(),
+        }
+    }
+}
@@ -1,9 +1,21 @@
 //! Common functions shared across all platforms
 
-use std::{fs, path::Path};
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use colored::Color;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+const ELLIPSIS: char = '…';
+const ANSI_RESET: &str = "\x1b[0m";
 
 const KIB_IN_MB: f64 = 1024.0;
 const KIB_IN_GB: f64 = 1024.0 * 1024.0; // We are declaring it as f64 as we'll use it as a float in this file to minimize casting
+const SECONDS_TO_HOURS: u64 = 3600;
+const MINUTES_TO_HOURS: u64 = 60;
 
 /// Gets content from a single line file and trims it
 ///
@@ -21,6 +33,297 @@ pub fn get_trimmed(path: &Path) -> Result<String, String> {
     }
 }
 
+/// Gets the value of a key from the contents of an os-release file, without its quotes.
+///
+/// For example: ID_LIKE="ubuntu debian" with the key ID_LIKE returns ubuntu debian
+pub fn get_os_release_value(content: &str, key: &str) -> Option<String> {
+    content
+        .lines()
+        .find_map(|line| line.trim().strip_prefix(key)?.strip_prefix('='))
+        .map(|value| value.trim().trim_matches(|c| c == '"' || c == '\'').to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Removes every escape sequence from the text, such as the colors added by the colored crate
+pub fn strip_ansi(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            stripped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            // CSI sequences (E.g: colors) go on until the final byte, which is in the @ to ~ range
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('@' ..= '~').contains(&c) {
+                        break;
+                    }
+                }
+            },
+            // OSC sequences (E.g: hyperlinks) end with BEL or with ESC \
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1b' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            },
+            // Any other escape sequence is a single character long
+            _ => {},
+        }
+    }
+
+    stripped
+}
+
+/// Gets the amount of terminal columns the text takes up. Escape sequences take up none and East
+/// Asian wide characters (E.g: CJK and most emojis) take up two, unlike .len() which counts bytes.
+///
+/// For example: "\x1b[1mOS:\x1b[0m 日本" is 8 columns wide
+pub fn get_display_width(text: &str) -> usize {
+    strip_ansi(text).width()
+}
+
+/// Gets the width of the terminal stdout is connected to, None if stdout is not a terminal
+/// (E.g: the output is piped into a file or another program)
+pub fn get_terminal_width() -> Option<usize> {
+    terminal_size::terminal_size_of(std::io::stdout()).map(|(width, _)| width.0 as usize)
+}
+
+/// Gets the height of the terminal stdout is connected to, None if stdout is not a terminal
+pub fn get_terminal_height() -> Option<usize> {
+    terminal_size::terminal_size_of(std::io::stdout()).map(|(_, height)| height.0 as usize)
+}
+
+/// A visible character alongside the escape sequences that come right before it
+struct AnsiCell {
+    escapes: String,
+    c: char,
+}
+
+/// Splits text into visible characters, keeping their escape sequences. Escape sequences at the
+/// very end of the text are returned separately
+fn split_ansi_cells(text: &str) -> (Vec<AnsiCell>, String) {
+    let mut cells = Vec::new();
+    let mut escapes = String::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            cells.push(AnsiCell {
+                escapes: std::mem::take(&mut escapes),
+                c,
+            });
+            continue;
+        }
+
+        // Only CSI sequences are kept, which is what the colored crate produces
+        escapes.push(c);
+        if chars.peek() == Some(&'[') {
+            escapes.push('[');
+            chars.next();
+            for c in chars.by_ref() {
+                escapes.push(c);
+                if ('@' ..= '~').contains(&c) {
+                    break;
+                }
+            }
+        }
+    }
+
+    (cells, escapes)
+}
+
+/// Cuts the text so that it fits into the given amount of columns, ending it with an ellipsis.
+/// Colors are kept and reset after the ellipsis, text that already fits is returned as is.
+///
+/// For example: "Intel Core i7" with a width of 8 becomes "Intel C…"
+pub fn truncate_to_width(text: &str, width: usize) -> String {
+    if get_display_width(text) <= width {
+        return text.to_string();
+    }
+
+    let (cells, _) = split_ansi_cells(text);
+    let mut truncated = String::new();
+    let mut used_width = 0;
+    let mut has_escapes = false;
+
+    for cell in cells {
+        let cell_width = cell.c.width().unwrap_or(0);
+        // One column is always left for the ellipsis
+        if used_width + cell_width + 1 > width {
+            break;
+        }
+        has_escapes |= !cell.escapes.is_empty();
+        truncated.push_str(&cell.escapes);
+        truncated.push(cell.c);
+        used_width += cell_width;
+    }
+
+    if width > 0 {
+        truncated.push(ELLIPSIS);
+    }
+    if has_escapes {
+        truncated.push_str(ANSI_RESET);
+    }
+    truncated
+}
+
+/// Splits the text into lines that fit into the given amount of columns, breaking on spaces when
+/// possible. Every line after the first starts with `indent` spaces, so that a value can be wrapped
+/// under itself instead of under its key. Colors are closed at the end of every line and reopened
+/// on the next one
+pub fn wrap_to_width(text: &str, width: usize, indent: usize) -> Vec<String> {
+    if get_display_width(text) <= width {
+        return vec![text.to_string()];
+    }
+    // An indent that leaves no room for the text would never end
+    let indent = if indent < width { indent } else { 0 };
+
+    let (cells, trailing_escapes) = split_ansi_cells(text);
+    let mut lines = Vec::new();
+    let mut active_escapes = String::new();
+    let mut start = 0;
+
+    while start < cells.len() {
+        let available = if lines.is_empty() { width } else { width - indent };
+        let line_start_escapes = active_escapes.clone();
+
+        // Finds how many cells fit into the line
+        let mut end = start;
+        let mut used_width = 0;
+        while end < cells.len() {
+            let cell_width = cells[end].c.width().unwrap_or(0);
+            if used_width + cell_width > available && end > start {
+                break;
+            }
+            used_width += cell_width;
+            end += 1;
+        }
+
+        // Breaks on the last space instead of inside a word, unless the word takes the whole line.
+        // The first line is never broken before the indent, which would leave a key alone
+        let min_break = if lines.is_empty() { indent.max(1) } else { start + 1 };
+        let mut next_start = end;
+        if end < cells.len()
+            && cells[end].c != ' '
+            && let Some(space) = (min_break .. end).rev().find(|&i| cells[i].c == ' ')
+        {
+            end = space;
+            next_start = space + 1;
+        }
+
+        let mut line = String::new();
+        if !lines.is_empty() {
+            line.push_str(&" ".repeat(indent));
+        }
+        line.push_str(&line_start_escapes);
+        for cell in &cells[start .. end] {
+            if cell.escapes.contains(ANSI_RESET) {
+                active_escapes.clear();
+            }
+            active_escapes.push_str(&cell.escapes);
+            line.push_str(&cell.escapes);
+            line.push(cell.c);
+        }
+        // Escapes of the skipped space still have to be applied to the next line
+        for cell in &cells[end .. next_start] {
+            active_escapes.push_str(&cell.escapes);
+        }
+
+        if next_start >= cells.len() {
+            line.push_str(&trailing_escapes);
+        } else if !active_escapes.is_empty() {
+            line.push_str(ANSI_RESET);
+        }
+        lines.push(line);
+
+        // Spaces at the start of a wrapped line would break the alignment
+        start = next_start;
+        while start < cells.len() && cells[start].c == ' ' {
+            active_escapes.push_str(&cells[start].escapes);
+            start += 1;
+        }
+    }
+
+    lines
+}
+
+/// Writes the contents into a temporary file inside the same directory and renames it over the
+/// target, so that the file is never seen half written. If the file already has the exact same
+/// contents it is not touched at all. The file keeps its permissions and, when running as root,
+/// its owner. Symlinks are followed, so that their target is replaced instead of the link.
+/// Returns true if the file has been written
+///
+/// # Errors
+/// Returns an error if the temporary file cannot be written or renamed, the temporary file is
+/// removed in that case
+pub fn write_file_atomically(path: &Path, contents: &[u8]) -> std::io::Result<bool> {
+    // E.g: /etc/motd is a link to /run/motd.dynamic on Debian, renaming over the link itself would
+    // turn it into a regular file
+    let path = &resolve_symlink(path);
+    if fs::read(path).is_ok_and(|current| current == contents) {
+        return Ok(false);
+    }
+
+    let file_name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    // The process id avoids collisions if two instances are writing the same file
+    let temporary_path = path.with_file_name(format!(".{}.{}.tmp", file_name, std::process::id()));
+
+    let result = write_temporary_file(&temporary_path, path, contents)
+        .and_then(|_| fs::rename(&temporary_path, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&temporary_path);
+    }
+    result?;
+
+    // The rename itself is only on disk once the directory is synced
+    let directory = path.parent().filter(|parent| !parent.as_os_str().is_empty());
+    fs::File::open(directory.unwrap_or(Path::new(".")))?.sync_all()?;
+    Ok(true)
+}
+
+/// Gets the file a path points to, even if it's a symlink whose target does not exist yet
+fn resolve_symlink(path: &Path) -> PathBuf {
+    fs::canonicalize(path)
+        .or_else(|_| {
+            let target = fs::read_link(path)?;
+            Ok::<_, std::io::Error>(path.parent().unwrap_or(Path::new("")).join(target))
+        })
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn write_temporary_file(
+    temporary_path: &Path,
+    path: &Path,
+    contents: &[u8],
+) -> std::io::Result<()> {
+    let mut file = fs::File::create(temporary_path)?;
+    file.write_all(contents)?;
+
+    // Keeps the mode and owner of the file being replaced instead of the umask defaults
+    if let Ok(metadata) = fs::metadata(path) {
+        file.set_permissions(metadata.permissions())?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            // Only root can give a file away, anyone else keeps owning the new file
+            let _ = std::os::unix::fs::fchown(&file, Some(metadata.uid()), Some(metadata.gid()));
+        }
+    }
+
+    // Otherwise a crash right after the rename can leave an empty file behind
+    file.sync_all()
+}
+
 /// Converts KiB figures into GB, MB or unchanged based on its size.
 /// Returns a formatted String based on the conversion that has happened
 ///
@@ -46,6 +349,21 @@ pub fn convert_to_bytes(memory_kib: f64) -> Result<String, String> {
     }
 }
 
+/// Formats an amount of seconds in HHh MMm SSs format, hours are omitted if they're 0.
+///
+/// For example: 3725 will return "01h 02m 05s"
+pub fn format_uptime(uptime_seconds: u64) -> String {
+    let hours = uptime_seconds / SECONDS_TO_HOURS;
+    let minutes = (uptime_seconds % SECONDS_TO_HOURS) / MINUTES_TO_HOURS;
+    let seconds = uptime_seconds % MINUTES_TO_HOURS;
+
+    if hours < 1 {
+        format!("{:02}m {:02}s", minutes, seconds)
+    } else {
+        format!("{:02}h {:02}m {:02}s", hours, minutes, seconds)
+    }
+}
+
 /// Gets the percentage as a rounded value considering a part and a total
 pub fn get_percentage_from_part(part: f64, total: f64) -> Result<u64, String> {
     if part < 0.0 || total < 0.0 {
@@ -114,3 +432,12 @@ pub fn strip_cpu_name(full_name: &str) -> String {
 
     full_name[..end_pos].trim().to_string()
 }
+
+/// Parses a color written in the config file, either a name (E.g: "blue", "bright red") or a hex
+/// color (E.g: "#ff8800" or "#f80")
+///
+/// # Errors
+/// Returns an error if the color is neither
+pub fn parse_color(color: &str) -> Result<Color, String> {
+    color.trim().parse().map_err(|_| format!("Unknown color: {}", color))
+}
@@ -2,8 +2,9 @@
 //! To regenerate the config file and test new setups just run
 //! cargo run -- --reset-config
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use clap::ValueEnum;
 use serde::{Deserialize, Serialize}; // This transforms toml files into structs and viceversa
 
 use crate::cli::Cli;
@@ -11,9 +12,149 @@ use crate::cli::Cli;
 #[derive(Debug, Deserialize, Serialize, Default)]
 pub struct Config {
     pub display: DisplayConfig,
+    // Sections added after [display] must have a default, since older config files do not have
+    // them
+    #[serde(default)]
+    pub motd: MotdConfig,
+    #[serde(default)]
+    pub check: CheckConfig,
+    #[serde(default)]
+    pub template: TemplateConfig,
+    #[serde(default)]
+    pub layout: LayoutConfig,
+    #[serde(default)]
+    pub logo: LogoConfig,
+    #[serde(default)]
+    pub colors: ColorsConfig,
+    #[serde(default)]
+    pub theme: ThemeConfig,
+    #[serde(default)]
+    pub icons: IconsConfig,
+    #[serde(default)]
+    pub progress_bars: ProgressBarsConfig,
+    #[serde(default)]
+    pub thresholds: ThresholdsConfig,
 }
 
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct MotdConfig {
+    /// Keeps the colors inside the file written by --motd, off by default since not every login
+    /// shell or terminal shows them correctly
+    pub ansi: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct TemplateConfig {
+    /// Same as --format-string, replaces the logo and the info with a single line when set
+    pub format_string: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LogoConfig {
+    /// Same as --logo, uses another built-in logo and its colors instead of the detected one
+    pub distro: Option<String>,
+    /// Same as --logo-file, "-" reads the logo from stdin
+    pub file: Option<PathBuf>,
+    /// Same as --logo-size, only applies to built-in logos
+    pub size: LogoSize,
+    /// Same as --logo-image, a PNG shown instead of the text logo
+    pub image: Option<PathBuf>,
+    /// Same as --image-protocol
+    pub image_protocol: ImageProtocol,
+    /// Same as --image-width, its rows follow from the aspect ratio of the image
+    pub image_width: usize,
+}
+
+impl Default for LogoConfig {
+    fn default() -> Self {
+        Self {
+            distro: None,
+            file: None,
+            size: LogoSize::Normal,
+            image: None,
+            image_protocol: ImageProtocol::Auto,
+            image_width: 30,
+        }
+    }
+}
+
+/// Shared by the config file and --image-protocol
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, ValueEnum, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageProtocol {
+    /// Detected from the environment variables set by the terminal
+    #[default]
+    Auto,
+    /// Kitty graphics protocol, also supported by WezTerm and Ghostty
+    Kitty,
+    /// Supported by foot, mlterm, Contour, xterm -ti vt340 exc
+    Sixel,
+    /// Truecolor text made of ▀ and ▄, works in any terminal and over plain SSH
+    #[value(name = "halfblocks")]
+    #[serde(rename = "halfblocks")]
+    HalfBlocks,
+}
+
+/// How the logo and the info are laid out inside the terminal
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LayoutConfig {
+    /// What happens to info lines that are wider than the terminal
+    pub overflow: Overflow,
+    /// Below this amount of columns only the info is shown, unless the logo is on top
+    pub logo_min_width: usize,
+    pub logo_position: LogoPosition,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            overflow: Overflow::Truncate,
+            logo_min_width: 60,
+            logo_position: LogoPosition::Left,
+        }
+    }
+}
+
+/// Shared by the config file and --logo-size
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, ValueEnum, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogoSize {
+    Small,
+    #[default]
+    Normal,
+    Large,
+    /// Picks the size that matches the amount of info lines and fits the terminal height
+    Auto,
+}
+
+/// Shared by the config file and --logo-position
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, ValueEnum, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogoPosition {
+    /// Logo on the left, info on the right
+    #[default]
+    Left,
+    /// Info on the left, logo on the right
+    Right,
+    /// Logo above the info, suits narrow terminals
+    Top,
+    /// Only the info
+    None,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Overflow {
+    /// Cuts the value and ends it with an ellipsis
+    #[default]
+    Truncate,
+    /// Continues the value on the next lines, under itself
+    Wrap,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
 pub struct DisplayConfig {
     // The names MUST match the names inside config.toml
     pub identifier: bool,
@@ -31,6 +172,9 @@ pub struct DisplayConfig {
     pub disk: bool,
     pub battery: bool,
     pub power_draw: bool,
+    // Modules added later must have a default, since older config files do not have them
+    #[serde(default)]
+    pub colors: bool,
 }
 
 impl Default for DisplayConfig {
@@ -52,12 +196,295 @@ impl Default for DisplayConfig {
             disk: true,
             battery: true,
             power_draw: false,
+            colors: false,
+        }
+    }
+}
+
+/// Looks of the keys and values of every module. Values that are not set come from the preset
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct ThemeConfig {
+    /// Same as --theme
+    pub preset: ThemePreset,
+    /// A color name (E.g: "blue", "bright red") or a hex color (E.g: "#ff8800")
+    pub key_color: Option<String>,
+    /// Any of "bold", "italic", "underline", "dimmed", an empty list leaves keys plain
+    pub key_style: Option<Vec<String>>,
+    pub value_color: Option<String>,
+    /// Goes between every key and its value
+    pub separator: Option<String>,
+    /// Repeated under the identifier, an empty string removes the underline
+    pub underline: Option<String>,
+    /// Paints the keys with the first color of the logo, takes precedence over key_color
+    pub keys_follow_logo: Option<bool>,
+}
+
+/// Shared by the config file and --theme
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, ValueEnum, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemePreset {
+    /// Bold keys followed by ": "
+    #[default]
+    Default,
+    /// Bold keys in the color of the logo
+    Distro,
+    /// Dimmed keys, no colons and no underline
+    Minimal,
+    /// Keys in the color of the logo followed by an arrow
+    Arrows,
+}
+
+/// Icons shown with the key of every module, E.g: "🧠 CPU". Custom icons are used even when the
+/// set is "none"
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct IconsConfig {
+    /// Same as --icons
+    pub set: IconSet,
+    pub mode: IconMode,
+    // The names MUST match the module names inside [display]
+    pub os: Option<String>,
+    pub kernel: Option<String>,
+    pub uptime: Option<String>,
+    pub cpu: Option<String>,
+    pub gpu: Option<String>,
+    pub screen: Option<String>,
+    pub ram: Option<String>,
+    pub swap: Option<String>,
+    pub disk: Option<String>,
+    /// Replaces the icons that change with the charge and the status
+    pub battery: Option<String>,
+    pub power_draw: Option<String>,
+}
+
+/// Shared by the config file and --icons
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, ValueEnum, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum IconSet {
+    /// Only custom icons
+    #[default]
+    None,
+    /// Needs a Nerd Font in the terminal
+    Nerd,
+    Emoji,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum IconMode {
+    /// The icon goes before the key, E.g: "🧠 CPU: ..."
+    #[default]
+    Prefix,
+    /// The icon takes the place of the key, E.g: "🧠: ..."
+    Replace,
+}
+
+/// Progress bars for the modules with a percentage, E.g: "[██████░░░░] 61%"
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ProgressBarsConfig {
+    pub mode: ProgressBarMode,
+    /// Any of "ram", "swap", "disk", "battery"
+    pub modules: Vec<String>,
+    /// Amount of glyphs inside the brackets
+    pub width: usize,
+    pub filled: String,
+    pub empty: String,
+    /// A color name or a hex color, the filled part follows the color of the percentage if unset
+    pub filled_color: Option<String>,
+    pub empty_color: Option<String>,
+}
+
+impl Default for ProgressBarsConfig {
+    fn default() -> Self {
+        Self {
+            mode: ProgressBarMode::None,
+            modules: ["ram", "swap", "disk", "battery"].map(String::from).to_vec(),
+            width: 10,
+            filled: String::from("█"),
+            empty: String::from("░"),
+            filled_color: None,
+            empty_color: None,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProgressBarMode {
+    /// Only the numbers, E.g: "1.2 GB / 2 GB (61%)"
+    #[default]
+    None,
+    /// The numbers followed by the bar, E.g: "1.2 GB / 2 GB [██████░░░░] 61%"
+    Both,
+    /// Only the bar and the percentage, E.g: "[██████░░░░] 61%"
+    Bar,
+}
+
+/// Where the percentages of every module change color, used by the colored text, the progress
+/// bars and the status bars. Modules without levels keep the built-in ones
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct ThresholdsConfig {
+    pub ram: Option<Vec<ThresholdLevelConfig>>,
+    pub swap: Option<Vec<ThresholdLevelConfig>>,
+    pub disk: Option<Vec<ThresholdLevelConfig>>,
+    /// Battery goes the other way around: its first level is the critical one
+    pub battery: Option<Vec<ThresholdLevelConfig>>,
+}
+
+/// A level starts at its percentage and goes on until the next one, E.g: { at = 80, color = "red" }
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ThresholdLevelConfig {
+    pub at: u64,
+    /// Name or hex color
+    pub color: String,
+}
+
+/// Looks of the colors module, the swatches of the 8 (or 16) terminal colors
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ColorsConfig {
+    pub style: ColorStyle,
+    /// Character used by the custom style
+    pub glyph: String,
+    /// Adds a second row with the bright colors
+    pub bright: bool,
+}
+
+impl Default for ColorsConfig {
+    fn default() -> Self {
+        Self {
+            style: ColorStyle::Blocks,
+            glyph: String::from("■"),
+            bright: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorStyle {
+    /// Blocks of background color, like in neofetch
+    #[default]
+    Blocks,
+    /// A colored ● for every color
+    Circles,
+    /// A colored glyph for every color, see ColorsConfig::glyph
+    Custom,
+}
+
+/// Thresholds used by --check, in percent. A module without thresholds is not checked.
+/// These are alerts mapping to the Nagios exit codes, unlike the color levels of [thresholds]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CheckConfig {
+    pub ram: CheckThresholds,
+    pub swap: CheckThresholds,
+    pub disk: CheckThresholds,
+    /// Battery is checked the other way around: lower capacity is worse
+    pub battery: CheckThresholds,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct CheckThresholds {
+    pub warn: Option<u64>,
+    pub crit: Option<u64>,
+}
+
+impl CheckThresholds {
+    pub fn new(warn: u64, crit: u64) -> Self {
+        Self {
+            warn: Some(warn),
+            crit: Some(crit),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.warn.is_none() && self.crit.is_none()
+    }
+}
+
+impl Default for CheckConfig {
+    fn default() -> Self {
+        Self {
+            ram: CheckThresholds::new(80, 95),
+            swap: CheckThresholds::new(80, 95),
+            disk: CheckThresholds::new(80, 95),
+            battery: CheckThresholds::new(30, 10),
+        }
+    }
+}
+
+impl CheckConfig {
+    /// Gets the names of the modules that have at least one threshold, as used by enable_only()
+    pub fn get_checked_modules(&self) -> Vec<String> {
+        [
+            ("ram", &self.ram),
+            ("swap", &self.swap),
+            ("disk", &self.disk),
+            ("battery", &self.battery),
+        ]
+        .into_iter()
+        .filter(|(_, thresholds)| !thresholds.is_empty())
+        .map(|(module, _)| module.to_string())
+        .collect()
+    }
+}
+
+impl DisplayConfig {
+    /// Turns off every module except the given ones, module names are the same as in config.toml.
+    /// Sub-options such as cpu_frequency are left as they are
+    ///
+    /// # Errors
+    /// Returns an error if a module name does not exist
+    pub fn enable_only(&mut self, modules: &[String]) -> Result<(), String> {
+        let mut enabled = DisplayConfig {
+            identifier: false,
+            os: false,
+            kernel: false,
+            uptime: false,
+            cpu: false,
+            gpu: false,
+            screen: false,
+            ram: false,
+            swap: false,
+            disk: false,
+            battery: false,
+            power_draw: false,
+            colors: false,
+            ..*self
+        };
+
+        for module in modules {
+            let toggle = match module.as_str() {
+                "identifier" => &mut enabled.identifier,
+                "os" => &mut enabled.os,
+                "kernel" => &mut enabled.kernel,
+                "uptime" => &mut enabled.uptime,
+                "cpu" => &mut enabled.cpu,
+                "gpu" => &mut enabled.gpu,
+                "screen" => &mut enabled.screen,
+                "ram" => &mut enabled.ram,
+                "swap" => &mut enabled.swap,
+                "disk" => &mut enabled.disk,
+                "battery" => &mut enabled.battery,
+                "power_draw" => &mut enabled.power_draw,
+                "colors" => &mut enabled.colors,
+                _ => return Err(format!("Unknown module: {}", module)),
+            };
+            *toggle = true;
+        }
+
+        *self = enabled;
+        Ok(())
+    }
+}
+
 fn get_config_template() -> String {
-    r#"# Rustfetch config file
+    // Delimited by ## since hex colors contain "#
+    r##"# Rustfetch config file
 
 [display]
 # Display username and host name
@@ -102,7 +529,134 @@ disk = true
 battery = true
 # Display power draw
 power_draw = false
-"#
+
+# Display the terminal colors below the info
+colors = false
+
+[motd]
+# Keep colors in the file written by --motd
+ansi = false
+
+[check]
+# Thresholds used by --check in percent, remove
+# a value to stop checking it (E.g: ram = {})
+# Battery is checked the other way around: a
+# warning is given when capacity goes below "warn"
+ram = { warn = 80, crit = 95 }
+swap = { warn = 80, crit = 95 }
+disk = { warn = 80, crit = 95 }
+battery = { warn = 30, crit = 10 }
+
+[template]
+# Prints a single line instead of the logo and the
+# info, same as --format-string. E.g:
+# format_string = "{os} | {cpu} | {ram.percent}%"
+
+[layout]
+# What to do with values wider than the terminal:
+# "truncate" them or "wrap" them under themselves
+overflow = "truncate"
+# The logo is hidden if the terminal has less
+# columns than this
+logo_min_width = 60
+# Where the logo goes: "left", "right", "top" or
+# "none", same as --logo-position
+logo_position = "left"
+
+[logo]
+# Uses another built-in logo and its colors, same
+# as --logo. E.g: distro = "arch"
+# Uses a custom logo file, same as --logo-file.
+# E.g: file = "/etc/rustfetch/logo.txt"
+# Size of the built-in logo: "small", "normal",
+# "large" or "auto", same as --logo-size
+size = "normal"
+# Shows a PNG instead of the text logo, same as
+# --logo-image.
+# E.g: image = "/etc/rustfetch/logo.png"
+# "auto", "kitty", "sixel" or "halfblocks", same as
+# --image-protocol
+image_protocol = "auto"
+# Columns taken by the image, same as --image-width
+image_width = 30
+
+[colors]
+# Looks of the colors module: "blocks", "circles"
+# or "custom", which uses the glyph below
+style = "blocks"
+glyph = "■"
+# Adds a second row with the bright colors
+bright = true
+
+[theme]
+# Built-in theme: "default", "distro", "minimal" or
+# "arrows", same as --theme. The options below
+# override the ones of the preset
+preset = "default"
+# Colors are names (E.g: "blue", "bright red") or
+# hex colors (E.g: "#ff8800")
+# key_color = "blue"
+# Any of "bold", "italic", "underline", "dimmed"
+# key_style = ["bold"]
+# value_color = "white"
+# separator = ": "
+# underline = "-"
+# Paints the keys with the color of the logo
+# keys_follow_logo = false
+
+[icons]
+# Icons next to the keys: "none", "nerd" (needs a
+# Nerd Font) or "emoji", same as --icons
+set = "none"
+# "prefix" puts the icon before the key, "replace"
+# shows the icon instead of the key
+mode = "prefix"
+# Custom icons for single modules, E.g:
+# cpu = "🧠"
+# battery = "⚡"
+
+[progress_bars]
+# Progress bars like [██████░░░░] for the modules
+# below: "none", "both" (numbers and bar) or "bar"
+# (only the bar and the percentage)
+mode = "none"
+modules = ["ram", "swap", "disk", "battery"]
+# Amount of glyphs inside the brackets
+width = 10
+filled = "█"
+empty = "░"
+# Colors are names or hex colors, the filled part
+# follows the color of the percentage unless set
+# filled_color = "blue"
+# empty_color = "bright black"
+
+[thresholds]
+# Colors of the percentages, every level starts at
+# its percentage and goes on until the next one.
+# Levels are not the alerts of --check: these only
+# color values, [check] decides when to alert
+ram = [
+    { at = 0, color = "green" },
+    { at = 40, color = "yellow" },
+    { at = 80, color = "red" },
+]
+swap = [
+    { at = 0, color = "green" },
+    { at = 40, color = "yellow" },
+    { at = 80, color = "red" },
+]
+disk = [
+    { at = 0, color = "green" },
+    { at = 40, color = "yellow" },
+    { at = 80, color = "red" },
+]
+# Lower is worse for batteries
+battery = [
+    { at = 0, color = "red" },
+    { at = 30, color = "yellow" },
+    { at = 70, color = "green" },
+]
+"##
     .to_string()
 }
 
@@ -115,7 +669,7 @@ pub fn get_default_path() -> PathBuf {
 }
 
 /// Creates the config file with default options and comments
-fn create_config_file(config_path: &PathBuf) -> Config {
+fn create_config_file(config_path: &Path) -> Config {
     let default_config = Config::default();
 
     // If parent directory does not exist, create it
@@ -129,7 +683,8 @@ fn create_config_file(config_path: &PathBuf) -> Config {
         eprintln!("Warning: Could not create config file at {:?}: {}", config_path, e);
         eprintln!("Using default configuration in memory");
     } else {
-        println!("Created default config file at:  {:?}", config_path);
+        // stderr, since stdout may be parsed (E.g: --json, --check)
+        eprintln!("Created default config file at:  {:?}", config_path);
     }
 
     default_config
@@ -142,7 +697,27 @@ pub fn load_config(cli: &Cli) -> Config {
         return create_config_file(&config_path);
     }
 
-    if let Ok(content) = std::fs::read_to_string(&config_path) {
+    load_config_file(&config_path)
+}
+
+/// Same as load_config() but a config file that can not be parsed is an error instead of falling
+/// back to the defaults, --check would otherwise go on with the default thresholds unnoticed
+///
+/// # Errors
+/// Returns an error if the config file exists but can not be parsed
+pub fn load_config_strict(cli: &Cli) -> Result<Config, String> {
+    let config_path = cli.config_file.as_ref().cloned().unwrap_or_else(get_default_path);
+
+    match std::fs::read_to_string(&config_path) {
+        Ok(content) if !cli.reset_config => toml::from_str(&content)
+            .map_err(|e| format!("Failed to parse config file {:?}: {}", config_path, e)),
+        _ => Ok(load_config(cli)),
+    }
+}
+
+/// Parses the config file at the given path, if it does not exist it gets created with defaults
+pub fn load_config_file(config_path: &Path) -> Config {
+    if let Ok(content) = std::fs::read_to_string(config_path) {
         // If parsing fails, use defaults instead
         toml::from_str(&content).unwrap_or_else(|e| {
             eprintln!("Warning: Failed to parse config file: {}", e);
@@ -150,28 +725,57 @@ pub fn load_config(cli: &Cli) -> Config {
             Config::default()
         })
     } else {
-        create_config_file(&config_path)
+        create_config_file(config_path)
     }
 }
 
+/// Default config with every module turned on, for --all. The config file is never read
 pub fn load_all_config() -> Config {
-    Config {
-        display: DisplayConfig {
-            identifier: true,
-            os: true,
-            kernel: true,
-            uptime: true,
-            cpu: true,
-            cpu_frequency: true,
-            gpu: true,
-            screen: true,
-            resolution: true,
-            refresh_rate: true,
-            ram: true,
-            swap: true,
-            disk: true,
-            battery: true,
-            power_draw: true,
-        },
+    Config::default().with_all_modules()
+}
+
+/// Reads only the [motd] section of the config file, so that --all --motd still follows it. The
+/// file is not created if it's missing and the defaults are used if it can not be parsed
+pub fn load_motd_config(cli: &Cli) -> MotdConfig {
+    let config_path = cli.config_file.as_ref().cloned().unwrap_or_else(get_default_path);
+
+    std::fs::read_to_string(config_path)
+        .ok()
+        .and_then(|content| toml::from_str::<MotdSection>(&content).ok())
+        .map(|section| section.motd)
+        .unwrap_or_default()
+}
+
+/// The other sections are ignored, so a broken [display] does not matter to --all
+#[derive(Deserialize)]
+struct MotdSection {
+    #[serde(default)]
+    motd: MotdConfig,
+}
+
+impl Config {
+    /// Turns on every module and sub-option, every other section is left untouched
+    pub fn with_all_modules(self) -> Self {
+        Self {
+            display: DisplayConfig {
+                identifier: true,
+                os: true,
+                kernel: true,
+                uptime: true,
+                cpu: true,
+                cpu_frequency: true,
+                gpu: true,
+                screen: true,
+                resolution: true,
+                refresh_rate: true,
+                ram: true,
+                swap: true,
+                disk: true,
+                battery: true,
+                power_draw: true,
+                colors: true,
+            },
+            ..self
+        }
     }
 }
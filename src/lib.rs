@@ -1,7 +1,23 @@
-//! Exposes all modules for testing
+//! Exposes all modules for testing and for using rustfetch as a library.
+//!
+//! The main entry point is collect(), which returns every module enabled in the config as typed
+//! values instead of colored text:
+//! ```no_run
+//! let config = rustfetch::Config::default();
+//! let report = rustfetch::collect(&config);
+//!
+//! if let Some(ram) = report.ram {
+//!     println!("{} / {} bytes", ram.used_bytes, ram.total_bytes);
+//! }
+//! ```
 pub mod cache;
 pub mod cli;
 pub mod common;
 pub mod config;
+pub mod output;
 pub mod platform;
+pub mod report;
 pub mod sysinfo;
+
+pub use config::{Config, DisplayConfig};
+pub use report::{SystemReport, collect};
@@ -1,16 +1,23 @@
-pub mod cache;
-pub mod cli;
-pub mod common;
-pub mod config;
-pub mod platform;
-pub mod sysinfo;
+use std::io::IsTerminal;
 
 use clap::Parser;
-
-use crate::{
-    cli::Cli,
-    common::{get_logo_lines, print_logo},
-    config::{load_all_config, load_config},
+use rustfetch::{
+    cache,
+    cli::{Cli, OutputFormat},
+    collect,
+    common::{
+        ColorDepth, Icons, ImageLogo, LOGO_IDS, Layout, ProgressBars, Theme, Thresholds,
+        choose_logo_size, display_colors, get_info_lines, get_logo_lines, get_logo_palette,
+        get_terminal_height, get_terminal_width, load_png, print_image_logo, print_logo,
+        read_logo_file, render_half_blocks, resolve_color_depth, resolve_image_protocol,
+        resolve_logo_id, set_color_depth, write_file_atomically, write_logo,
+    },
+    config::{
+        ImageProtocol, get_default_path, load_all_config, load_config, load_config_strict,
+        load_motd_config,
+    },
+    output::{self, BarProtocol},
+    platform,
 };
 
 // TODO:
@@ -18,46 +25,195 @@ use crate::{
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
-    let config = if cli.all {
-        load_all_config()
+
+    if cli.check {
+        // Errors would exit with 1, which monitoring tools read as WARNING instead of UNKNOWN
+        let exit_code = run(&cli).map_or_else(|e| output::print_check_error(&e), |_| 0);
+        std::process::exit(exit_code);
+    }
+
+    run(&cli)
+}
+
+fn run(cli: &Cli) -> Result<(), Box<dyn std::error::Error>> {
+    let is_terminal = std::io::stdout().is_terminal();
+    let color_depth = resolve_color_depth(cli.color, is_terminal, |key| std::env::var(key).ok());
+    // colored only decides whether to paint, how many colors are used is up to fit_color()
+    colored::control::set_override(color_depth != ColorDepth::None);
+    set_color_depth(color_depth);
+
+    if cli.bug_report {
+        // Before loading the config, which would create it if it's missing
+        let config_path = cli.config_file.clone().unwrap_or_else(get_default_path);
+        return output::print_bug_report(&config_path);
+    }
+
+    let mut config = if cli.check {
+        load_config_strict(cli)?
+    } else if cli.all {
+        let mut config = load_all_config();
+        if cli.motd.is_some() {
+            config.motd = load_motd_config(cli);
+        }
+        config
     } else {
-        load_config(&cli)
+        load_config(cli)
+    };
+
+    if !cli.modules.is_empty() {
+        config.display.enable_only(&cli.modules)?;
+    }
+
+    // Only the GPU module uses the cache, probing wgpu for nothing would just slow the run down
+    if cli.clear_cache && config.display.gpu {
+        cache::create_cache()?;
+    }
+
+    let bar_protocol = match cli.output_format() {
+        OutputFormat::Waybar => Some(BarProtocol::Waybar),
+        OutputFormat::I3bar => Some(BarProtocol::I3bar),
+        OutputFormat::Polybar => Some(BarProtocol::Polybar),
+        _ => None,
     };
-    let sys = sysinfo::create_system(&config);
+    if let Some(protocol) = bar_protocol {
+        // Status bars collect the report on their own, once per refresh
+        return output::run_status_bar(&config, protocol, cli.interval);
+    }
+
+    if cli.check {
+        // Only the modules with thresholds are collected, the others would just slow the check down
+        config.display.enable_only(&config.check.get_checked_modules())?;
+        let exit_code = output::print_check(&collect(&config), &config.check)?;
+        std::process::exit(exit_code);
+    }
+
+    let format_string = cli.format_string.as_ref().or(config.template.format_string.as_ref());
+    if let Some(format_string) = format_string
+        && cli.output_format() == OutputFormat::Text
+        && cli.export.is_none()
+        && cli.motd.is_none()
+    {
+        let template = output::Template::parse(format_string)?;
+        // Placeholders decide what gets collected, regardless of the config and --modules
+        template.enable_modules(&mut config.display)?;
+        println!("{}", template.render(&collect(&config)));
+        return Ok(());
+    }
+
+    if cli.motd.is_some() && !platform::has_display() {
+        // Login banners are mostly shown on headless servers, where there's no screen to show
+        config.display.screen = false;
+    }
+
+    let report = collect(&config);
+
+    match cli.output_format() {
+        OutputFormat::Text => {},
+        OutputFormat::Json => return output::print_json(&report),
+        OutputFormat::Env => return output::print_env(&report),
+        OutputFormat::Prometheus => return output::print_prometheus(&report),
+        OutputFormat::Waybar | OutputFormat::I3bar | OutputFormat::Polybar => {},
+    }
+
+    if cli.export.is_some() {
+        // Colors are always needed to be converted, even if stdout is not a terminal
+        colored::control::set_override(true);
+        set_color_depth(ColorDepth::TrueColor);
+    } else if cli.motd.is_some() {
+        // The banner is written to a file, so whether stdout is a terminal (E.g: from cron) does
+        // not matter, --color never and NO_COLOR still do
+        let motd_depth = resolve_color_depth(cli.color, true, |key| std::env::var(key).ok());
+        let motd_depth = if config.motd.ansi { motd_depth } else { ColorDepth::None };
+        colored::control::set_override(motd_depth != ColorDepth::None);
+        set_color_depth(motd_depth);
+    }
 
     let distro_id = platform::get_distro_id();
 
-    let info_lines: Vec<String> = config
-        .display
-        .identifier
-        .then(common::display_identifier)
-        .into_iter()
-        .flatten()
-        .chain(
-            vec![
-                config.display.os.then(common::display_os),
-                config.display.kernel.then(common::display_kernel),
-                config.display.cpu.then(|| common::display_cpu(&sys, &config)),
-                #[cfg(target_os = "linux")]
-                config.display.gpu.then(|| common::display_gpu_name(&cli)).flatten(),
-                config.display.screen.then(|| common::display_screen(&config)).flatten(),
-                config.display.ram.then(|| common::display_ram_usage(&sys)),
-                config.display.swap.then(|| common::display_swap_usage(&sys)),
-                config.display.uptime.then(common::display_uptime),
-                #[cfg(target_os = "linux")]
-                config.display.battery.then(common::display_battery).flatten(),
-                #[cfg(target_os = "linux")]
-                config.display.power_draw.then(common::display_power_draw).flatten(),
-                config.display.disk.then(common::display_disk_usage),
-            ]
-            .into_iter()
-            .flatten(),
-        )
-        .collect();
-
-    let logo_lines = get_logo_lines(&distro_id);
-
-    print_logo(logo_lines, info_lines, &distro_id, &cli)?;
+    // The logo id decides which built-in logo and which colors are used, a logo file only
+    // replaces the art
+    let logo_id = match cli.logo.as_ref().or(config.logo.distro.as_ref()) {
+        Some(logo) if !LOGO_IDS.contains(&logo.as_str()) => {
+            return Err(format!("Unknown logo: {}", logo).into());
+        },
+        Some(logo) => logo.clone(),
+        // Distros without a logo get the one of the distro they are based on, or Tux
+        None => resolve_logo_id(&distro_id, &platform::get_distro_like_ids()),
+    };
+
+    let mut theme = Theme::new(&config.theme, cli.theme)?;
+    theme.follow_logo(get_logo_palette(&logo_id).first().copied());
+    theme.icons = Icons::new(&config.icons, cli.icons);
+    theme.progress_bars = ProgressBars::new(&config.progress_bars)?;
+    theme.thresholds = Thresholds::new(&config.thresholds)?;
+
+    let mut info_lines = get_info_lines(&report, &theme);
+    // The colors are not part of the report, they go below the info after an empty line
+    if config.display.colors {
+        info_lines.push(String::new());
+        info_lines.extend(display_colors(&config.colors));
+    }
+
+    // Exports and login banners are not shown in this terminal, so its size does not matter
+    let to_terminal = cli.export.is_none() && cli.motd.is_none();
+    let width = cli
+        .width
+        .map(usize::from)
+        .or_else(|| to_terminal.then(get_terminal_width).flatten());
+    let height = to_terminal.then(get_terminal_height).flatten();
+
+    let logo_image = match cli.logo_image.as_ref().or(config.logo.image.as_ref()) {
+        Some(path) => Some(load_png(path)?),
+        None => None,
+    };
+    let image_width = cli.image_width.map(usize::from).unwrap_or(config.logo.image_width);
+    // Graphics protocols only work when drawn straight into this terminal, pipes, exports and
+    // login banners get half-blocks
+    let image_protocol = if to_terminal && is_terminal {
+        let image_protocol = cli.image_protocol.unwrap_or(config.logo.image_protocol);
+        resolve_image_protocol(image_protocol, |key| std::env::var(key).ok())
+    } else {
+        ImageProtocol::HalfBlocks
+    };
+
+    let logo_lines = match (&logo_image, cli.logo_file.as_ref().or(config.logo.file.as_ref())) {
+        (Some(image), _) if image_protocol == ImageProtocol::HalfBlocks => {
+            render_half_blocks(image, image_width)
+        },
+        // Drawn by print_image_logo() below
+        (Some(_), _) => Vec::new(),
+        (None, Some(path)) => read_logo_file(path)?,
+        (None, None) => {
+            let logo_size = cli.logo_size.unwrap_or(config.logo.size);
+            let logo_size = choose_logo_size(&logo_id, logo_size, info_lines.len(), height);
+            get_logo_lines(&logo_id, logo_size)
+        },
+    };
+    let layout = Layout::new(cli, &config.layout, width, &theme.separator);
+
+    if let Some(format) = cli.export {
+        let mut rendered = Vec::new();
+        write_logo(&mut rendered, logo_lines, info_lines, &logo_id, &layout)?;
+        print!("{}", output::export_ansi(&String::from_utf8(rendered)?, format));
+        return Ok(());
+    }
+
+    if let Some(path) = &cli.motd {
+        let mut rendered = Vec::new();
+        write_logo(&mut rendered, logo_lines, info_lines, &logo_id, &layout)?;
+        write_file_atomically(path, &rendered)
+            .map_err(|e| format!("Could not write {}: {}", path.display(), e))?;
+        return Ok(());
+    }
+
+    if let Some(image) = &logo_image
+        && image_protocol != ImageProtocol::HalfBlocks
+    {
+        let image = ImageLogo::new(image, image_protocol, image_width);
+        return print_image_logo(&image, info_lines, &layout);
+    }
+
+    print_logo(logo_lines, info_lines, &logo_id, &layout)?;
 
     Ok(())
 }
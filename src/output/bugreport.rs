@@ -0,0 +1,125 @@
+//! Markdown summary printed by --bug-report, meant to be pasted into GitHub issues. It contains
+//! everything needed to triage a wrong value without asking the user for more info: version,
+//! distro id, which files and adapters every module used, the cache and config paths and the raw
+//! value of every module without colors
+
+use std::{fmt::Write as _, io::Write, path::Path};
+
+use crate::{
+    cache::{get_cache_path, read_cache},
+    config::Config,
+    platform,
+    report::{GpuInfo, SystemReport, collect},
+};
+
+/// Everything shown in the bug report, gathered beforehand so that rendering can be tested
+pub struct BugReport {
+    pub version: String,
+    pub target: String,
+    pub distro_id: String,
+    pub config_path: String,
+    pub cache_path: String,
+    pub data_sources: Vec<(&'static str, String)>,
+    pub report: SystemReport,
+}
+
+/// Gathers the bug report with every module turned on, regardless of the config. Nothing is
+/// written to disk: the config is never created and the cache is only read
+pub fn print_bug_report(config_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let cache = read_cache().map_err(|e| e.to_string());
+    let cached_gpu = match &cache {
+        Ok(cache) => {
+            format!("{} ({}:{})", cache.gpu_name_pretty, cache.gpu_vendor_id, cache.gpu_device_id)
+        },
+        Err(e) => format!("Unavailable ({})", e),
+    };
+
+    // The GPU module would create or refresh the cache, so the GPU is looked up apart
+    let mut config = Config::default().with_all_modules();
+    config.display.gpu = false;
+    let mut report = collect(&config);
+    let gpu_name = platform::find_gpu_name(cache.as_ref().ok());
+    report.gpu = gpu_name.as_ref().map(|(name, _)| GpuInfo { name: name.clone() });
+
+    // Comparing the name source with the cache shows whether a stale cache is behind a wrong GPU
+    // name
+    let (gpu_name_source, wgpu_adapter) = match gpu_name {
+        Some((name, "wgpu")) => (String::from("wgpu"), name),
+        Some((_, source)) => (source.to_string(), format!("Not probed, named by {}", source)),
+        None => (String::from("Unavailable"), String::from("Unavailable")),
+    };
+    let mut data_sources = platform::get_data_sources();
+    data_sources.push(("Cached GPU", cached_gpu));
+    data_sources.push(("GPU name source", gpu_name_source));
+    data_sources.push(("wgpu adapter", wgpu_adapter));
+
+    let bug_report = BugReport {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        target: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+        distro_id: platform::get_distro_id(),
+        config_path: describe_path(config_path),
+        cache_path: describe_path(&get_cache_path()),
+        data_sources,
+        report,
+    };
+
+    let mut handle = std::io::stdout().lock();
+    write!(handle, "{}", get_bug_report_markdown(&bug_report))?;
+    handle.flush()?;
+    Ok(())
+}
+
+pub fn get_bug_report_markdown(bug_report: &BugReport) -> String {
+    let mut markdown = String::new();
+
+    let _ = writeln!(markdown, "### rustfetch bug report\n");
+    let _ = writeln!(markdown, "| Info | Value |");
+    let _ = writeln!(markdown, "| --- | --- |");
+    for (info, value) in [
+        ("Version", &bug_report.version),
+        ("Target", &bug_report.target),
+        ("Distro ID", &bug_report.distro_id),
+        ("Config", &bug_report.config_path),
+        ("Cache", &bug_report.cache_path),
+    ] {
+        let _ = writeln!(markdown, "| {} | {} |", info, format_cell(value));
+    }
+
+    let _ = writeln!(markdown, "\n#### Data sources\n");
+    let _ = writeln!(markdown, "| Source | Value |");
+    let _ = writeln!(markdown, "| --- | --- |");
+    for (source, value) in &bug_report.data_sources {
+        let _ = writeln!(markdown, "| {} | {} |", source, format_cell(value));
+    }
+
+    let _ = writeln!(markdown, "\n#### Modules\n");
+    let _ = writeln!(markdown, "| Module | Value |");
+    let _ = writeln!(markdown, "| --- | --- |");
+    for (path, value) in bug_report.report.fields() {
+        let value = match value {
+            serde_json::Value::String(string) => string,
+            value => value.to_string(),
+        };
+        let _ = writeln!(markdown, "| {} | {} |", path, format_cell(&value));
+    }
+    // Modules that were not detected are just as important as the detected ones
+    if let Ok(serde_json::Value::Object(modules)) = serde_json::to_value(&bug_report.report) {
+        for (module, _) in modules.iter().filter(|(_, value)| value.is_null()) {
+            let _ = writeln!(markdown, "| {} | Unavailable |", module);
+        }
+    }
+
+    markdown
+}
+
+/// Wraps a value in inline code so that Markdown does not interpret it, pipes would still break the
+/// table so they are escaped. For example: a|b becomes `a\|b`
+pub fn format_cell(value: &str) -> String {
+    let value = value.replace('`', "'").replace('|', r"\|").replace('\n', " ");
+    format!("`{}`", value)
+}
+
+fn describe_path(path: &Path) -> String {
+    let status = if path.exists() { "found" } else { "missing" };
+    format!("{} ({})", path.display(), status)
+}
@@ -0,0 +1,162 @@
+//! Monitoring check through --check, which behaves like a Nagios plugin: it prints a single status
+//! line followed by perfdata and exits with 0 (OK), 1 (WARNING), 2 (CRITICAL) or 3 (UNKNOWN).
+//! Thresholds come from the [check] section of the config file
+
+use std::io::Write;
+
+use crate::{
+    config::{CheckConfig, CheckThresholds},
+    report::SystemReport,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CheckStatus {
+    Ok,
+    Warning,
+    Critical,
+    Unknown,
+}
+
+impl CheckStatus {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Ok => 0,
+            Self::Warning => 1,
+            Self::Critical => 2,
+            Self::Unknown => 3,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ok => "OK",
+            Self::Warning => "WARNING",
+            Self::Critical => "CRITICAL",
+            Self::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+/// Outcome of every check, status is the worst one among them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckResult {
+    pub status: CheckStatus,
+    pub line: String,
+}
+
+/// Prints the status line and returns the exit code the program should use
+pub fn print_check(report: &SystemReport, check_config: &CheckConfig) -> std::io::Result<i32> {
+    let result = run_checks(report, check_config);
+
+    let mut handle = std::io::stdout().lock();
+    writeln!(handle, "{}", result.line)?;
+    handle.flush()?;
+    Ok(result.status.exit_code())
+}
+
+/// Prints an UNKNOWN status line for errors of rustfetch itself (E.g: an unknown module or a
+/// config file that can not be parsed) and returns its exit code. They must not end up as exit
+/// code 1, which monitoring tools would read as WARNING
+pub fn print_check_error(error: &dyn std::fmt::Display) -> i32 {
+    println!("RUSTFETCH UNKNOWN - {}", error);
+    CheckStatus::Unknown.exit_code()
+}
+
+/// Checks every module with thresholds against the report. Modules that are not available on the
+/// machine (E.g: battery on a desktop) or without thresholds are skipped, if nothing could be
+/// checked the status is UNKNOWN
+pub fn run_checks(report: &SystemReport, check_config: &CheckConfig) -> CheckResult {
+    // (label, perfdata label, percentage, status, thresholds, whether lower is worse)
+    let mut checks = Vec::new();
+
+    if let Some(ram) = &report.ram {
+        let status = check_usage(ram.percent, &check_config.ram);
+        checks.push(("RAM", "ram", ram.percent, status, check_config.ram, false));
+    }
+    if let Some(swap) = &report.swap
+        && swap.total_bytes > 0
+    {
+        let status = check_usage(swap.percent, &check_config.swap);
+        checks.push(("Swap", "swap", swap.percent, status, check_config.swap, false));
+    }
+    if let Some(disk) = &report.disk {
+        let status = check_usage(disk.percent, &check_config.disk);
+        checks.push(("Disk", "disk", disk.percent, status, check_config.disk, false));
+    }
+    if let Some(battery) = &report.battery {
+        let percentage = battery.capacity_percent;
+        let status = check_capacity(percentage, &check_config.battery);
+        checks.push(("Battery", "battery", percentage, status, check_config.battery, true));
+    }
+
+    // Modules without thresholds are not checked at all
+    checks.retain(|check| !check.4.is_empty());
+
+    let Some(status) = checks.iter().map(|check| check.3).max() else {
+        return CheckResult {
+            status: CheckStatus::Unknown,
+            line: String::from("RUSTFETCH UNKNOWN - No module could be checked"),
+        };
+    };
+
+    let summary: Vec<String> = checks
+        .iter()
+        .map(|(label, _, percentage, ..)| format!("{} {}%", label, percentage))
+        .collect();
+    let perfdata: Vec<String> = checks
+        .iter()
+        .map(|(_, perfdata_label, percentage, _, thresholds, inverse)| {
+            format!(
+                "{}={}%;{};{};0;100",
+                perfdata_label,
+                percentage,
+                format_threshold(thresholds.warn, *inverse),
+                format_threshold(thresholds.crit, *inverse)
+            )
+        })
+        .collect();
+
+    CheckResult {
+        status,
+        line: format!(
+            "RUSTFETCH {} - {} | {}",
+            status.as_str(),
+            summary.join(", "),
+            perfdata.join(" ")
+        ),
+    }
+}
+
+/// Usage percentages are worse the higher they are. A threshold is only triggered once it's
+/// exceeded, E.g: 80% is still OK with warn = 80, just like the "80" range of the perfdata
+pub fn check_usage(percentage: u64, thresholds: &CheckThresholds) -> CheckStatus {
+    if thresholds.crit.is_some_and(|crit| percentage > crit) {
+        CheckStatus::Critical
+    } else if thresholds.warn.is_some_and(|warn| percentage > warn) {
+        CheckStatus::Warning
+    } else {
+        CheckStatus::Ok
+    }
+}
+
+/// Capacity percentages are worse the lower they are. A threshold is only triggered once the
+/// capacity goes below it, just like the "30:" range of the perfdata
+pub fn check_capacity(percentage: u64, thresholds: &CheckThresholds) -> CheckStatus {
+    if thresholds.crit.is_some_and(|crit| percentage < crit) {
+        CheckStatus::Critical
+    } else if thresholds.warn.is_some_and(|warn| percentage < warn) {
+        CheckStatus::Warning
+    } else {
+        CheckStatus::Ok
+    }
+}
+
+/// Formats a threshold as a Nagios range. A plain "80" alerts above 80, while capacities alert
+/// below the threshold so they get "30:" instead. Both match check_usage() and check_capacity()
+pub fn format_threshold(threshold: Option<u64>, inverse: bool) -> String {
+    match threshold {
+        Some(value) if inverse => format!("{}:", value),
+        Some(value) => value.to_string(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,61 @@
+//! Shell-eval output through --format env, every value of the report becomes a variable such as
+//! RUSTFETCH_RAM_USED_BYTES=8053063680 so that `eval "$(rustfetch --format env)"` works in any
+//! POSIX shell. Unavailable values are not printed at all
+
+use std::io::Write;
+
+use crate::report::SystemReport;
+
+const VARIABLE_PREFIX: &str = "RUSTFETCH_";
+
+pub fn print_env(report: &SystemReport) -> Result<(), Box<dyn std::error::Error>> {
+    let mut handle = std::io::stdout().lock();
+
+    for line in get_env_lines(report) {
+        writeln!(handle, "{}", line)?;
+    }
+
+    handle.flush()?;
+    Ok(())
+}
+
+/// Converts the report into NAME=value lines, strings are always quoted while numbers are not
+pub fn get_env_lines(report: &SystemReport) -> Vec<String> {
+    report
+        .fields()
+        .into_iter()
+        .map(|(path, value)| {
+            let value = match value {
+                serde_json::Value::String(string) => shell_quote(&string),
+                // Numbers and booleans cannot contain characters the shell would interpret
+                value => value.to_string(),
+            };
+            format!("{}={}", get_variable_name(&path), value)
+        })
+        .collect()
+}
+
+/// Transforms a report path into a variable name, for example "ram.used_bytes" becomes
+/// "RUSTFETCH_RAM_USED_BYTES"
+pub fn get_variable_name(path: &str) -> String {
+    let name: String = path
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}{}", VARIABLE_PREFIX, name)
+}
+
+/// Quotes a value so that a POSIX shell reads it back literally. Single quotes disable every
+/// expansion, so the only character to handle is the single quote itself, which is closed, escaped
+/// and reopened.
+///
+/// For example: it's becomes 'it'\''s'
+pub fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
@@ -0,0 +1,270 @@
+//! Exports the logo and the info lines through --export svg or --export html, so that the fetch
+//! can be shared without taking a screenshot. The layout is rendered by write_logo() exactly as in
+//! the terminal and its ANSI colors are then converted into SVG/HTML styles.
+//! Nothing depends on the time or on the terminal, so the same input always produces the same bytes
+
+use std::fmt::Write as _;
+
+use crate::{
+    cli::ExportFormat,
+    common::{get_256_color_rgb, get_display_width},
+};
+
+const FONT_SIZE: u32 = 14;
+const LINE_HEIGHT: u32 = 18;
+// Width of a monospace character is roughly 0.6 times the font size
+const CHAR_WIDTH: f64 = 8.4;
+const MARGIN: u32 = 16;
+const BACKGROUND_COLOR: &str = "#1e1e1e";
+const FOREGROUND_COLOR: &str = "#d4d4d4";
+const FONT_FAMILY: &str = "'DejaVu Sans Mono', 'Menlo', 'Consolas', monospace";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SpanStyle {
+    pub color: Option<(u8, u8, u8)>,
+    pub background: Option<(u8, u8, u8)>,
+    pub bold: bool,
+}
+
+/// A piece of text that shares the same style
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StyledSpan {
+    pub text: String,
+    pub style: SpanStyle,
+}
+
+/// Converts text containing ANSI colors (E.g: the output of write_logo()) into the given format
+pub fn export_ansi(ansi_text: &str, format: ExportFormat) -> String {
+    let lines: Vec<Vec<StyledSpan>> = ansi_text.lines().map(parse_ansi_line).collect();
+
+    match format {
+        ExportFormat::Svg => render_svg(&lines),
+        ExportFormat::Html => render_html(&lines),
+    }
+}
+
+/// Splits a line into spans according to its SGR escape sequences (bold, 16, 256 and truecolor
+/// foregrounds and backgrounds). Any other escape sequence is dropped
+pub fn parse_ansi_line(line: &str) -> Vec<StyledSpan> {
+    let mut spans = Vec::new();
+    let mut style = SpanStyle::default();
+    let mut text = String::new();
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            text.push(c);
+            continue;
+        }
+        if chars.peek() != Some(&'[') {
+            continue;
+        }
+        chars.next();
+
+        // Parameters go on until the final byte, which is in the @ to ~ range
+        let mut parameters = String::new();
+        let mut final_byte = None;
+        for c in chars.by_ref() {
+            if ('@' ..= '~').contains(&c) {
+                final_byte = Some(c);
+                break;
+            }
+            parameters.push(c);
+        }
+        if final_byte != Some('m') {
+            continue;
+        }
+
+        let mut new_style = style;
+        apply_sgr_parameters(&parameters, &mut new_style);
+        if new_style != style && !text.is_empty() {
+            spans.push(StyledSpan {
+                text: std::mem::take(&mut text),
+                style,
+            });
+        }
+        style = new_style;
+    }
+
+    if !text.is_empty() {
+        spans.push(StyledSpan { text, style });
+    }
+    spans
+}
+
+fn apply_sgr_parameters(parameters: &str, style: &mut SpanStyle) {
+    // An empty sequence (ESC[m) is the same as a reset
+    let codes: Vec<u16> = parameters.split(';').map(|code| code.parse().unwrap_or(0)).collect();
+    let mut i = 0;
+
+    while i < codes.len() {
+        match codes[i] {
+            0 => *style = SpanStyle::default(),
+            1 => style.bold = true,
+            22 => style.bold = false,
+            // The bright colors come right after the 8 basic ones
+            code @ 30 ..= 37 => style.color = Some(get_256_color_rgb((code - 30) as u8)),
+            code @ 90 ..= 97 => style.color = Some(get_256_color_rgb((code - 90 + 8) as u8)),
+            39 => style.color = None,
+            code @ 40 ..= 47 => style.background = Some(get_256_color_rgb((code - 40) as u8)),
+            code @ 100 ..= 107 => {
+                style.background = Some(get_256_color_rgb((code - 100 + 8) as u8));
+            },
+            49 => style.background = None,
+            38 => {
+                let (color, length) = parse_extended_color(&codes[i + 1 ..]);
+                style.color = color.or(style.color);
+                i += length;
+            },
+            48 => {
+                let (color, length) = parse_extended_color(&codes[i + 1 ..]);
+                style.background = color.or(style.background);
+                i += length;
+            },
+            // Other attributes (E.g: italic, underline) are not exported
+            _ => {},
+        }
+        i += 1;
+    }
+}
+
+/// Parses the parameters following a 38 or 48, either "5;n" (256 colors) or "2;r;g;b"
+/// (truecolor). Returns the color and how many parameters it took, values above 255 give no
+/// color, like in terminals, but their parameters are still taken
+fn parse_extended_color(codes: &[u16]) -> (Option<(u8, u8, u8)>, usize) {
+    match codes {
+        [2, r, g, b, ..] => {
+            let color = match (u8::try_from(*r), u8::try_from(*g), u8::try_from(*b)) {
+                (Ok(r), Ok(g), Ok(b)) => Some((r, g, b)),
+                _ => None,
+            };
+            (color, 4)
+        },
+        [5, index, ..] => (u8::try_from(*index).ok().map(get_256_color_rgb), 2),
+        _ => (None, 0),
+    }
+}
+
+fn render_svg(lines: &[Vec<StyledSpan>]) -> String {
+    let columns = lines.iter().map(|spans| get_line_width(spans)).max().unwrap_or(0);
+    let width = (MARGIN * 2) as f64 + (columns as f64 * CHAR_WIDTH).ceil();
+    let height = MARGIN * 2 + lines.len() as u32 * LINE_HEIGHT;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#,
+        width, height
+    );
+    let _ = writeln!(svg, r#"<rect width="100%" height="100%" fill="{}"/>"#, BACKGROUND_COLOR);
+    let _ = writeln!(
+        svg,
+        r#"<g font-family="{}" font-size="{}" fill="{}" xml:space="preserve">"#,
+        FONT_FAMILY, FONT_SIZE, FOREGROUND_COLOR
+    );
+
+    for (i, spans) in lines.iter().enumerate() {
+        // Backgrounds go below the text, E.g: the swatches of the colors module
+        let mut column = 0;
+        for span in spans {
+            let width = get_display_width(&span.text);
+            if let Some(background) = span.style.background {
+                let _ = writeln!(
+                    svg,
+                    r#"<rect x="{:.1}" y="{}" width="{:.1}" height="{}" fill="{}"/>"#,
+                    MARGIN as f64 + column as f64 * CHAR_WIDTH,
+                    MARGIN + i as u32 * LINE_HEIGHT,
+                    width as f64 * CHAR_WIDTH,
+                    LINE_HEIGHT,
+                    format_hex_color(background)
+                );
+            }
+            column += width;
+        }
+
+        let y = MARGIN + FONT_SIZE + i as u32 * LINE_HEIGHT;
+        let _ = write!(svg, r#"<text x="{}" y="{}">"#, MARGIN, y);
+        for span in spans {
+            let mut attributes = String::new();
+            if let Some(color) = span.style.color {
+                let _ = write!(attributes, r#" fill="{}""#, format_hex_color(color));
+            }
+            if span.style.bold {
+                attributes.push_str(r#" font-weight="bold""#);
+            }
+            let _ = write!(svg, "<tspan{}>{}</tspan>", attributes, escape_xml(&span.text));
+        }
+        let _ = writeln!(svg, "</text>");
+    }
+
+    let _ = writeln!(svg, "</g>");
+    let _ = writeln!(svg, "</svg>");
+    svg
+}
+
+fn render_html(lines: &[Vec<StyledSpan>]) -> String {
+    let mut html = String::new();
+    let _ = writeln!(html, "<!DOCTYPE html>");
+    let _ = writeln!(html, "<html>");
+    let _ = writeln!(html, "<head>");
+    let _ = writeln!(html, r#"<meta charset="utf-8">"#);
+    let _ = writeln!(html, "<title>rustfetch</title>");
+    let _ = writeln!(
+        html,
+        "<style>pre {{ display: inline-block; margin: 0; padding: {}px; background: {}; color: \
+         {}; font-family: {}; font-size: {}px; line-height: {}px; }}</style>",
+        MARGIN, BACKGROUND_COLOR, FOREGROUND_COLOR, FONT_FAMILY, FONT_SIZE, LINE_HEIGHT
+    );
+    let _ = writeln!(html, "</head>");
+    let _ = writeln!(html, "<body>");
+    let _ = write!(html, "<pre>");
+
+    for spans in lines {
+        for span in spans {
+            let mut styles = Vec::new();
+            if let Some(color) = span.style.color {
+                styles.push(format!("color: {}", format_hex_color(color)));
+            }
+            if let Some(background) = span.style.background {
+                styles.push(format!("background-color: {}", format_hex_color(background)));
+            }
+            if span.style.bold {
+                styles.push(String::from("font-weight: bold"));
+            }
+
+            if styles.is_empty() {
+                html.push_str(&escape_xml(&span.text));
+            } else {
+                let _ = write!(
+                    html,
+                    r#"<span style="{}">{}</span>"#,
+                    styles.join("; "),
+                    escape_xml(&span.text)
+                );
+            }
+        }
+        html.push('\n');
+    }
+
+    let _ = writeln!(html, "</pre>");
+    let _ = writeln!(html, "</body>");
+    let _ = writeln!(html, "</html>");
+    html
+}
+
+fn get_line_width(spans: &[StyledSpan]) -> usize {
+    spans.iter().map(|span| get_display_width(&span.text)).sum()
+}
+
+fn format_hex_color((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// Escapes the characters that have a meaning in both XML (SVG) and HTML
+pub fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
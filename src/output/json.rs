@@ -0,0 +1,15 @@
+//! Machine readable output through --json, the document is the SystemReport serialized as is.
+//! Byte values are integers, percentages are numbers and unavailable modules are null
+
+use std::io::Write;
+
+use crate::report::SystemReport;
+
+pub fn print_json(report: &SystemReport) -> Result<(), Box<dyn std::error::Error>> {
+    let json = serde_json::to_string_pretty(report)?;
+
+    let mut handle = std::io::stdout().lock();
+    writeln!(handle, "{}", json)?;
+    handle.flush()?;
+    Ok(())
+}
@@ -0,0 +1,23 @@
+mod bugreport;
+pub use bugreport::*;
+
+mod check;
+pub use check::*;
+
+mod env;
+pub use env::*;
+
+mod export;
+pub use export::*;
+
+mod json;
+pub use json::*;
+
+mod prometheus;
+pub use prometheus::*;
+
+mod statusbar;
+pub use statusbar::*;
+
+mod template;
+pub use template::*;
@@ -0,0 +1,194 @@
+//! Prometheus output through --format prometheus, meant for node-exporter's textfile collector.
+//! Numeric values become gauges while strings (CPU name, OS, kernel exc) become labels of the
+//! rustfetch_info metric, which always has a value of 1
+
+use std::{fmt::Write as _, io::Write};
+
+use crate::report::SystemReport;
+
+struct Gauge {
+    name: &'static str,
+    help: &'static str,
+    labels: Vec<(&'static str, String)>,
+    value: f64,
+}
+
+impl Gauge {
+    fn new(name: &'static str, help: &'static str, value: f64) -> Self {
+        Self {
+            name,
+            help,
+            labels: Vec::new(),
+            value,
+        }
+    }
+
+    fn with_label(mut self, key: &'static str, value: &str) -> Self {
+        self.labels.push((key, value.to_string()));
+        self
+    }
+}
+
+pub fn print_prometheus(report: &SystemReport) -> Result<(), Box<dyn std::error::Error>> {
+    let mut handle = std::io::stdout().lock();
+    write!(handle, "{}", get_prometheus_metrics(report))?;
+    handle.flush()?;
+    Ok(())
+}
+
+/// Renders the report in the Prometheus text exposition format, every gauge has its own HELP and
+/// TYPE lines. Unavailable modules are not exported
+pub fn get_prometheus_metrics(report: &SystemReport) -> String {
+    let mut output = String::new();
+
+    for gauge in get_gauges(report) {
+        let _ = writeln!(output, "# HELP {} {}", gauge.name, gauge.help);
+        let _ = writeln!(output, "# TYPE {} gauge", gauge.name);
+        let _ = writeln!(output, "{}{} {}", gauge.name, format_labels(&gauge.labels), gauge.value);
+    }
+
+    output
+}
+
+fn get_gauges(report: &SystemReport) -> Vec<Gauge> {
+    let mut gauges = vec![get_info_gauge(report)];
+
+    if let Some(cpu) = &report.cpu
+        && let Some(frequency) = cpu.frequency_mhz
+    {
+        gauges.push(Gauge::new(
+            "rustfetch_cpu_frequency_hertz",
+            "Current CPU frequency in hertz",
+            (frequency * 1_000_000) as f64,
+        ));
+    }
+    if let Some(refresh_rate) = report.screen.as_ref().and_then(|s| s.refresh_rate_hz) {
+        gauges.push(Gauge::new(
+            "rustfetch_screen_refresh_rate_hertz",
+            "Refresh rate of the main screen in hertz",
+            refresh_rate as f64,
+        ));
+    }
+    if let Some(ram) = &report.ram {
+        gauges.push(Gauge::new(
+            "rustfetch_ram_total_bytes",
+            "Total RAM in bytes",
+            ram.total_bytes as f64,
+        ));
+        gauges.push(Gauge::new(
+            "rustfetch_ram_used_bytes",
+            "Used RAM in bytes",
+            ram.used_bytes as f64,
+        ));
+    }
+    if let Some(swap) = &report.swap {
+        gauges.push(Gauge::new(
+            "rustfetch_swap_total_bytes",
+            "Total swap in bytes",
+            swap.total_bytes as f64,
+        ));
+        gauges.push(Gauge::new(
+            "rustfetch_swap_used_bytes",
+            "Used swap in bytes",
+            swap.used_bytes as f64,
+        ));
+    }
+    if let Some(uptime) = &report.uptime {
+        gauges.push(Gauge::new(
+            "rustfetch_uptime_seconds",
+            "System uptime in seconds",
+            uptime.seconds as f64,
+        ));
+    }
+    if let Some(battery) = &report.battery {
+        gauges.push(Gauge::new(
+            "rustfetch_battery_capacity_percent",
+            "Battery capacity in percent",
+            battery.capacity_percent as f64,
+        ));
+        // A status label would start a new series on every plug and unplug
+        let is_charging = battery.status == "Charging";
+        gauges.push(Gauge::new(
+            "rustfetch_battery_charging",
+            "Whether the battery is charging, 1 or 0",
+            if is_charging { 1.0 } else { 0.0 },
+        ));
+    }
+    if let Some(power_draw) = &report.power_draw {
+        gauges.push(Gauge::new(
+            "rustfetch_power_draw_watts",
+            "Current power draw from the battery in watts",
+            power_draw.watts,
+        ));
+    }
+    if let Some(disk) = &report.disk {
+        gauges.push(
+            Gauge::new(
+                "rustfetch_disk_total_bytes",
+                "Total disk space in bytes",
+                disk.total_bytes as f64,
+            )
+            .with_label("mountpoint", &disk.mount_point),
+        );
+        gauges.push(
+            Gauge::new(
+                "rustfetch_disk_used_bytes",
+                "Used disk space in bytes",
+                disk.used_bytes as f64,
+            )
+            .with_label("mountpoint", &disk.mount_point),
+        );
+    }
+
+    gauges
+}
+
+/// Every string value is a label of rustfetch_info, labels of unavailable modules are omitted
+fn get_info_gauge(report: &SystemReport) -> Gauge {
+    let mut gauge = Gauge::new("rustfetch_info", "Static system information as labels", 1.0);
+
+    if let Some(identifier) = &report.identifier {
+        gauge = gauge.with_label("username", &identifier.username);
+        if let Some(host_name) = &identifier.host_name {
+            gauge = gauge.with_label("host_name", host_name);
+        }
+    }
+    if let Some(os) = &report.os {
+        gauge = gauge.with_label("os", &os.name).with_label("arch", &os.arch);
+    }
+    if let Some(kernel) = &report.kernel {
+        gauge = gauge.with_label("kernel", &format!("{} {}", kernel.name, kernel.version));
+    }
+    if let Some(cpu) = &report.cpu {
+        gauge = gauge.with_label("cpu", &cpu.name);
+    }
+    if let Some(gpu) = &report.gpu {
+        gauge = gauge.with_label("gpu", &gpu.name);
+    }
+    if let Some(resolution) = report.screen.as_ref().and_then(|s| s.resolution) {
+        gauge =
+            gauge.with_label("resolution", &format!("{}x{}", resolution.width, resolution.height));
+    }
+
+    gauge
+}
+
+fn format_labels(labels: &[(&str, String)]) -> String {
+    if labels.is_empty() {
+        return String::new();
+    }
+
+    let labels: Vec<String> = labels
+        .iter()
+        .map(|(key, value)| format!("{}=\"{}\"", key, escape_label_value(value)))
+        .collect();
+    format!("{{{}}}", labels.join(","))
+}
+
+/// Escapes a label value as required by the exposition format: backslashes, double quotes and line
+/// feeds are the only characters that must be escaped.
+///
+/// For example: Intel "Core" becomes Intel \"Core\"
+pub fn escape_label_value(value: &str) -> String {
+    value.replace('\\', r"\\").replace('"', "\\\"").replace('\n', r"\n")
+}
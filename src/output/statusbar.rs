@@ -0,0 +1,300 @@
+//! Status bar output through --format waybar, i3bar or polybar. Every module is turned into a
+//! block with a short text and a tooltip, percentages are highlighted with the same thresholds as
+//! the colored text but as CSS classes (ok, warn, critical) or bar colors.
+//! With --interval the modules are collected again and printed forever, as bars expect
+
+use std::{
+    io::{ErrorKind, Write},
+    thread::sleep,
+    time::Duration,
+};
+
+use serde::Serialize;
+
+use crate::{
+    common::{
+        BYTES_IN_GB, PercentageLevel, PercentageThresholds, Thresholds, format_memory,
+        format_uptime, round_to_two_decimal,
+    },
+    config::Config,
+    report::{SystemReport, collect},
+};
+
+// Same colors used by i3status for good, degraded and bad values
+const OK_COLOR: &str = "#00FF00";
+const WARN_COLOR: &str = "#FFFF00";
+const CRITICAL_COLOR: &str = "#FF0000";
+// Same as i3status, used when i3bar is run without --interval
+const I3BAR_DEFAULT_INTERVAL: u64 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarProtocol {
+    Waybar,
+    I3bar,
+    Polybar,
+}
+
+/// A single module as shown in a status bar
+#[derive(Debug, Clone, PartialEq)]
+pub struct BarBlock {
+    /// Same name as in config.toml
+    pub name: &'static str,
+    pub text: String,
+    pub tooltip: String,
+    pub level: Option<PercentageLevel>,
+    pub percentage: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct WaybarOutput<'a> {
+    text: String,
+    tooltip: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    class: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    percentage: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct I3barBlock<'a> {
+    name: &'a str,
+    full_text: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: Option<&'a str>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    urgent: bool,
+}
+
+/// Prints the modules enabled in the config once or, if an interval is given, every `interval`
+/// seconds until the bar closes stdout. i3bar always refreshes, every 5 seconds by default
+pub fn run_status_bar(
+    config: &Config,
+    protocol: BarProtocol,
+    interval: Option<u64>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let thresholds = Thresholds::new(&config.thresholds)?;
+    let interval = match protocol {
+        // i3bar expects an endless stream, a single line would leave the bar empty once read
+        BarProtocol::I3bar => Some(interval.unwrap_or(I3BAR_DEFAULT_INTERVAL)),
+        _ => interval,
+    };
+
+    match write_status_lines(config, protocol, interval, &thresholds) {
+        // The bar closed stdout (E.g: it was reloaded), which is how it tells us to stop
+        Err(e) if e.kind() == ErrorKind::BrokenPipe => Ok(()),
+        result => Ok(result?),
+    }
+}
+
+fn write_status_lines(
+    config: &Config,
+    protocol: BarProtocol,
+    interval: Option<u64>,
+    thresholds: &Thresholds,
+) -> std::io::Result<()> {
+    let mut handle = std::io::stdout().lock();
+
+    if protocol == BarProtocol::I3bar {
+        // The i3bar protocol is a header followed by an endless array of status lines
+        writeln!(handle, "{{\"version\":1}}")?;
+        writeln!(handle, "[")?;
+    }
+
+    let mut is_first = true;
+    loop {
+        let blocks = get_bar_blocks(&collect(config), thresholds);
+        writeln!(handle, "{}", get_status_line(protocol, &blocks, is_first)?)?;
+        handle.flush()?;
+        is_first = false;
+
+        let Some(seconds) = interval else {
+            return Ok(());
+        };
+        sleep(Duration::from_secs(seconds));
+    }
+}
+
+/// Gets the line printed on every refresh. i3bar lines are elements of a JSON array, so every line
+/// after the first one starts with the comma separating it from the previous one
+pub fn get_status_line(
+    protocol: BarProtocol,
+    blocks: &[BarBlock],
+    is_first: bool,
+) -> Result<String, serde_json::Error> {
+    Ok(match protocol {
+        BarProtocol::Waybar => get_waybar_line(blocks)?,
+        BarProtocol::I3bar if is_first => get_i3bar_line(blocks)?,
+        BarProtocol::I3bar => format!(",{}", get_i3bar_line(blocks)?),
+        BarProtocol::Polybar => get_polybar_line(blocks),
+    })
+}
+
+/// Renders every available module of the report as a block, in the same order as the text output
+pub fn get_bar_blocks(report: &SystemReport, thresholds: &Thresholds) -> Vec<BarBlock> {
+    let mut blocks = Vec::new();
+
+    if let Some(identifier) = &report.identifier {
+        let text =
+            format!("{}@{}", identifier.username, identifier.host_name.as_deref().unwrap_or(""));
+        blocks.push(get_text_block("identifier", text));
+    }
+    if let Some(os) = &report.os {
+        blocks.push(BarBlock {
+            tooltip: format!("OS: {} ({})", os.name, os.arch),
+            ..get_text_block("os", os.name.clone())
+        });
+    }
+    if let Some(kernel) = &report.kernel {
+        blocks.push(get_text_block("kernel", format!("{} {}", kernel.name, kernel.version)));
+    }
+    if let Some(cpu) = &report.cpu {
+        let text = match cpu.frequency_mhz {
+            Some(frequency) => {
+                format!("{} @ {} GHz", cpu.name, round_to_two_decimal(frequency as f64 / 1000.0))
+            },
+            None => cpu.name.clone(),
+        };
+        blocks.push(get_text_block("cpu", text));
+    }
+    if let Some(gpu) = &report.gpu {
+        blocks.push(get_text_block("gpu", gpu.name.clone()));
+    }
+    if let Some(screen) = &report.screen {
+        let mut parts = Vec::new();
+        if let Some(resolution) = screen.resolution {
+            parts.push(format!("{}x{}", resolution.width, resolution.height));
+        }
+        if let Some(refresh_rate) = screen.refresh_rate_hz {
+            parts.push(format!("{}Hz", refresh_rate));
+        }
+        blocks.push(get_text_block("screen", parts.join(" @ ")));
+    }
+    if let Some(ram) = &report.ram {
+        blocks.push(get_usage_block(
+            "ram",
+            "RAM",
+            ram.percent,
+            format!("{} / {}", format_memory(ram.used_bytes), format_memory(ram.total_bytes)),
+            &thresholds.ram,
+        ));
+    }
+    if let Some(swap) = &report.swap {
+        if swap.total_bytes == 0 {
+            blocks.push(BarBlock {
+                tooltip: String::from("Swap: Disabled"),
+                ..get_text_block("swap", String::from("Swap off"))
+            });
+        } else {
+            blocks.push(get_usage_block(
+                "swap",
+                "Swap",
+                swap.percent,
+                format!("{} / {}", format_memory(swap.used_bytes), format_memory(swap.total_bytes)),
+                &thresholds.swap,
+            ));
+        }
+    }
+    if let Some(uptime) = &report.uptime {
+        blocks.push(get_text_block("uptime", format_uptime(uptime.seconds)));
+    }
+    if let Some(battery) = &report.battery {
+        let percentage = battery.capacity_percent;
+        blocks.push(BarBlock {
+            name: "battery",
+            text: format!("BAT {}%", percentage),
+            tooltip: format!("Battery: {}% ({})", percentage, battery.status),
+            level: Some(thresholds.battery.get_level(percentage as f64)),
+            percentage: Some(percentage),
+        });
+    }
+    if let Some(power_draw) = &report.power_draw {
+        blocks.push(get_text_block("power_draw", format!("{}W", power_draw.watts)));
+    }
+    if let Some(disk) = &report.disk {
+        blocks.push(get_usage_block(
+            "disk",
+            &disk.mount_point,
+            disk.percent,
+            format!("{}GB / {}GB", disk.used_bytes / BYTES_IN_GB, disk.total_bytes / BYTES_IN_GB),
+            &thresholds.disk,
+        ));
+    }
+
+    blocks
+}
+
+/// Waybar expects a single JSON object per line. Multiple modules are merged into one block: texts
+/// are joined, the class is the worst level among them and the percentage is the first available
+pub fn get_waybar_line(blocks: &[BarBlock]) -> Result<String, serde_json::Error> {
+    let output = WaybarOutput {
+        text: blocks.iter().map(|b| b.text.as_str()).collect::<Vec<_>>().join(" | "),
+        tooltip: blocks.iter().map(|b| b.tooltip.as_str()).collect::<Vec<_>>().join("\n"),
+        class: blocks.iter().filter_map(|b| b.level).max().map(|level| level.as_str()),
+        percentage: blocks.iter().find_map(|b| b.percentage),
+    };
+    serde_json::to_string(&output)
+}
+
+/// Gets one status line of the i3bar protocol: an array with one block per module
+pub fn get_i3bar_line(blocks: &[BarBlock]) -> Result<String, serde_json::Error> {
+    let i3bar_blocks: Vec<I3barBlock> = blocks
+        .iter()
+        .map(|block| I3barBlock {
+            name: block.name,
+            full_text: &block.text,
+            color: block.level.map(get_level_color),
+            urgent: block.level == Some(PercentageLevel::Critical),
+        })
+        .collect();
+    serde_json::to_string(&i3bar_blocks)
+}
+
+/// Polybar reads plain text lines, levels are shown through its %{F} color tags. A % inside a
+/// value is escaped as %%, otherwise something like "%{" would be read as a tag
+pub fn get_polybar_line(blocks: &[BarBlock]) -> String {
+    blocks
+        .iter()
+        .map(|block| {
+            let text = block.text.replace('%', "%%");
+            match block.level {
+                Some(level) => format!("%{{F{}}}{}%{{F-}}", get_level_color(level), text),
+                None => text,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+fn get_level_color(level: PercentageLevel) -> &'static str {
+    match level {
+        PercentageLevel::Ok => OK_COLOR,
+        PercentageLevel::Warn => WARN_COLOR,
+        PercentageLevel::Critical => CRITICAL_COLOR,
+    }
+}
+
+fn get_text_block(name: &'static str, text: String) -> BarBlock {
+    BarBlock {
+        name,
+        tooltip: text.clone(),
+        text,
+        level: None,
+        percentage: None,
+    }
+}
+
+fn get_usage_block(
+    name: &'static str,
+    label: &str,
+    percentage: u64,
+    details: String,
+    thresholds: &PercentageThresholds,
+) -> BarBlock {
+    BarBlock {
+        name,
+        text: format!("{} {}%", label, percentage),
+        tooltip: format!("{}: {} ({}%)", label, details, percentage),
+        level: Some(thresholds.get_level(percentage as f64)),
+        percentage: Some(percentage),
+    }
+}
@@ -0,0 +1,243 @@
+//! One-line output through --format-string or format_string inside [template], meant for tmux
+//! status lines and shell prompts. E.g: "{os} | {cpu} | {ram.used}/{ram.total} ({ram.percent}%)"
+//! Use {{ and }} for literal braces
+
+use crate::{
+    common::{BYTES_IN_GB, format_frequency, format_memory, format_uptime},
+    config::DisplayConfig,
+    report::SystemReport,
+};
+
+/// Every supported placeholder alongside the module it needs, module names are the same as in
+/// config.toml
+pub const PLACEHOLDERS: &[(&str, &str)] = &[
+    ("identifier", "identifier"),
+    ("username", "identifier"),
+    ("host_name", "identifier"),
+    ("os", "os"),
+    ("os.name", "os"),
+    ("os.arch", "os"),
+    ("kernel", "kernel"),
+    ("kernel.name", "kernel"),
+    ("kernel.version", "kernel"),
+    ("cpu", "cpu"),
+    ("cpu.name", "cpu"),
+    ("cpu.frequency", "cpu"),
+    ("gpu", "gpu"),
+    ("screen", "screen"),
+    ("screen.resolution", "screen"),
+    ("screen.refresh_rate", "screen"),
+    ("ram", "ram"),
+    ("ram.used", "ram"),
+    ("ram.total", "ram"),
+    ("ram.percent", "ram"),
+    ("swap", "swap"),
+    ("swap.used", "swap"),
+    ("swap.total", "swap"),
+    ("swap.percent", "swap"),
+    ("uptime", "uptime"),
+    ("battery", "battery"),
+    ("battery.percent", "battery"),
+    ("battery.status", "battery"),
+    ("power_draw", "power_draw"),
+    ("disk", "disk"),
+    ("disk.used", "disk"),
+    ("disk.total", "disk"),
+    ("disk.percent", "disk"),
+    ("disk.mount_point", "disk"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplatePart {
+    Text(String),
+    Placeholder(&'static str),
+}
+
+/// A parsed format string, parsing it beforehand makes unknown placeholders fail before anything
+/// is collected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    pub parts: Vec<TemplatePart>,
+}
+
+impl Template {
+    /// # Errors
+    /// Returns an error on unknown placeholders and on braces that are not closed or escaped
+    pub fn parse(format_string: &str) -> Result<Self, String> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut chars = format_string.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                },
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                },
+                '{' => {
+                    let mut name = String::new();
+                    let mut closed = false;
+                    for c in chars.by_ref() {
+                        if c == '}' {
+                            closed = true;
+                            break;
+                        }
+                        name.push(c);
+                    }
+                    if !closed {
+                        return Err(format!("Unclosed placeholder: {{{}", name));
+                    }
+
+                    let placeholder = PLACEHOLDERS
+                        .iter()
+                        .find(|(placeholder, _)| *placeholder == name.trim())
+                        .map(|(placeholder, _)| *placeholder)
+                        .ok_or_else(|| format!("Unknown placeholder: {{{}}}", name))?;
+
+                    if !text.is_empty() {
+                        parts.push(TemplatePart::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(TemplatePart::Placeholder(placeholder));
+                },
+                '}' => return Err(String::from("Unmatched }, use }} for a literal brace")),
+                c => text.push(c),
+            }
+        }
+
+        if !text.is_empty() {
+            parts.push(TemplatePart::Text(text));
+        }
+        Ok(Self { parts })
+    }
+
+    /// Gets the modules needed by the placeholders, without duplicates
+    pub fn get_modules(&self) -> Vec<String> {
+        let mut modules: Vec<String> = Vec::new();
+
+        for part in &self.parts {
+            if let TemplatePart::Placeholder(name) = part
+                && let Some((_, module)) = PLACEHOLDERS.iter().find(|(p, _)| p == name)
+                && !modules.iter().any(|m| m == module)
+            {
+                modules.push(module.to_string());
+            }
+        }
+
+        modules
+    }
+
+    /// Turns on only what the placeholders need, the same way --modules does. Placeholders that
+    /// refer to a sub-option (E.g: {cpu.frequency}) turn it on as well
+    ///
+    /// # Errors
+    /// Returns an error if a placeholder refers to a module that does not exist in the config
+    pub fn enable_modules(&self, display: &mut DisplayConfig) -> Result<(), String> {
+        display.enable_only(&self.get_modules())?;
+
+        for part in &self.parts {
+            match part {
+                TemplatePart::Placeholder("cpu.frequency") => display.cpu_frequency = true,
+                TemplatePart::Placeholder("screen.resolution") => display.resolution = true,
+                TemplatePart::Placeholder("screen.refresh_rate") => display.refresh_rate = true,
+                _ => {},
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Renders the template with the values of the report, unavailable values are left empty
+    pub fn render(&self, report: &SystemReport) -> String {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                TemplatePart::Text(text) => text.clone(),
+                TemplatePart::Placeholder(name) => {
+                    get_placeholder_value(name, report).unwrap_or_default()
+                },
+            })
+            .collect()
+    }
+}
+
+/// Gets the plain text value of a placeholder, without colors or labels
+pub fn get_placeholder_value(name: &str, report: &SystemReport) -> Option<String> {
+    match name {
+        "identifier" => report.identifier.as_ref().map(|identifier| {
+            format!("{}@{}", identifier.username, identifier.host_name.as_deref().unwrap_or(""))
+        }),
+        "username" => report.identifier.as_ref().map(|i| i.username.clone()),
+        "host_name" => report.identifier.as_ref().and_then(|i| i.host_name.clone()),
+        "os" | "os.name" => report.os.as_ref().map(|os| os.name.clone()),
+        "os.arch" => report.os.as_ref().map(|os| os.arch.clone()),
+        "kernel" => report.kernel.as_ref().map(|k| format!("{} {}", k.name, k.version)),
+        "kernel.name" => report.kernel.as_ref().map(|k| k.name.clone()),
+        "kernel.version" => report.kernel.as_ref().map(|k| k.version.clone()),
+        "cpu" => report.cpu.as_ref().map(|cpu| match cpu.frequency_mhz {
+            Some(frequency) => format!("{} @ {}", cpu.name, format_frequency(frequency)),
+            None => cpu.name.clone(),
+        }),
+        "cpu.name" => report.cpu.as_ref().map(|cpu| cpu.name.clone()),
+        "cpu.frequency" => report
+            .cpu
+            .as_ref()
+            .and_then(|cpu| cpu.frequency_mhz)
+            .map(format_frequency),
+        "gpu" => report.gpu.as_ref().map(|gpu| gpu.name.clone()),
+        "screen" => report.screen.as_ref().map(|screen| {
+            let mut parts = Vec::new();
+            if let Some(resolution) = screen.resolution {
+                parts.push(format!("{}x{}", resolution.width, resolution.height));
+            }
+            if let Some(refresh_rate) = screen.refresh_rate_hz {
+                parts.push(format!("{}Hz", refresh_rate));
+            }
+            parts.join(" @ ")
+        }),
+        "screen.resolution" => report
+            .screen
+            .as_ref()
+            .and_then(|screen| screen.resolution)
+            .map(|resolution| format!("{}x{}", resolution.width, resolution.height)),
+        "screen.refresh_rate" => report
+            .screen
+            .as_ref()
+            .and_then(|screen| screen.refresh_rate_hz)
+            .map(|refresh_rate| format!("{}Hz", refresh_rate)),
+        "ram" => report.ram.as_ref().map(|ram| {
+            format!("{} / {}", format_memory(ram.used_bytes), format_memory(ram.total_bytes))
+        }),
+        "ram.used" => report.ram.as_ref().map(|ram| format_memory(ram.used_bytes)),
+        "ram.total" => report.ram.as_ref().map(|ram| format_memory(ram.total_bytes)),
+        "ram.percent" => report.ram.as_ref().map(|ram| ram.percent.to_string()),
+        "swap" => report.swap.as_ref().map(|swap| match swap.total_bytes {
+            0 => String::from("Disabled"),
+            _ => {
+                format!("{} / {}", format_memory(swap.used_bytes), format_memory(swap.total_bytes))
+            },
+        }),
+        "swap.used" => report.swap.as_ref().map(|swap| format_memory(swap.used_bytes)),
+        "swap.total" => report.swap.as_ref().map(|swap| format_memory(swap.total_bytes)),
+        "swap.percent" => report.swap.as_ref().map(|swap| swap.percent.to_string()),
+        "uptime" => report.uptime.as_ref().map(|uptime| format_uptime(uptime.seconds)),
+        "battery" => report
+            .battery
+            .as_ref()
+            .map(|battery| format!("{}% ({})", battery.capacity_percent, battery.status)),
+        "battery.percent" => report.battery.as_ref().map(|b| b.capacity_percent.to_string()),
+        "battery.status" => report.battery.as_ref().map(|b| b.status.clone()),
+        "power_draw" => report.power_draw.as_ref().map(|p| format!("{}W", p.watts as u32)),
+        "disk" => report.disk.as_ref().map(|disk| {
+            format!("{}GB / {}GB", disk.used_bytes / BYTES_IN_GB, disk.total_bytes / BYTES_IN_GB)
+        }),
+        "disk.used" => report.disk.as_ref().map(|d| format!("{}GB", d.used_bytes / BYTES_IN_GB)),
+        "disk.total" => report.disk.as_ref().map(|d| format!("{}GB", d.total_bytes / BYTES_IN_GB)),
+        "disk.percent" => report.disk.as_ref().map(|disk| disk.percent.to_string()),
+        "disk.mount_point" => report.disk.as_ref().map(|disk| disk.mount_point.clone()),
+        _ => None,
+    }
+}
@@ -1,8 +1,7 @@
 use std::{fs, path::Path};
 
 use crate::{
-    cache::{create_cache, get_cache},
-    cli::Cli,
+    cache::{Cache, create_cache, get_cache},
     common::*,
     sysinfo::*,
 };
@@ -11,20 +10,32 @@ const BATTERY_CAPACITY_DIR: &str = "/sys/class/power_supply/BAT0/capacity";
 const BATTERY_STATUS_DIR: &str = "/sys/class/power_supply/BAT0/status";
 const BATTERY_POWER_DRAW_DIR: &str = "/sys/class/power_supply/BAT0/power_now";
 const ROOT_DIR: &str = "/";
+const OS_RELEASE_PATH: &str = "/etc/os-release";
+// On some systems the indexes might start at 1, so instead of iterating through every single
+// possible "card*" we try the first two which are the most likely
+const GPU_DIR: &str = "/sys/class/drm/card0/device";
+const FALLBACK_GPU_DIR: &str = "/sys/class/drm/card1/device";
+const PCI_IDS_PATH: &str = "/usr/share/hwdata/pci.ids";
+const FALLBACK_PCI_IDS_PATH: &str = "/usr/share/misc/pci.ids";
+pub const KERNEL_NAME: &str = "Linux";
 
 pub fn get_distro_id() -> String {
-    fs::read_to_string(Path::new("/etc/os-release"))
+    fs::read_to_string(Path::new(OS_RELEASE_PATH))
         .ok()
-        .and_then(|content| {
-            content
-                .lines()
-                .find(|line| line.starts_with("ID="))
-                .and_then(|line| line.split('=').nth(1))
-                .map(|id| id.trim_matches('"').to_string())
-        })
+        .and_then(|content| get_os_release_value(&content, "ID"))
         .unwrap_or_else(|| "unknown".to_string())
 }
 
+/// Gets the ids of the distros this one is based on from ID_LIKE, closest first.
+/// E.g: Kubuntu returns ["ubuntu", "debian"]
+pub fn get_distro_like_ids() -> Vec<String> {
+    fs::read_to_string(Path::new(OS_RELEASE_PATH))
+        .ok()
+        .and_then(|content| get_os_release_value(&content, "ID_LIKE"))
+        .map(|ids| ids.split_whitespace().map(|id| id.to_string()).collect())
+        .unwrap_or_default()
+}
+
 /// Gets battery status as a tuple (Capacity, Status) if available
 pub fn get_battery() -> (String, String) {
     let capacity =
@@ -36,28 +47,34 @@ pub fn get_battery() -> (String, String) {
 
 /// Gets current power draw and returns it as Watts - Only available on battery-powered devices
 pub fn get_power_draw() -> u32 {
-    match get_trimmed(Path::new(BATTERY_POWER_DRAW_DIR)) {
-        // power_now contains the value in microwatts, we transform it in watts
-        Ok(content) => content.parse::<u32>().unwrap_or(0) / 1_000_000,
-        Err(_) => 0,
-    }
+    // power_now contains the value in microwatts, we transform it in watts
+    get_power_draw_microwatts().map_or(0, |microwatts| (microwatts / 1_000_000) as u32)
 }
 
-pub fn get_disk_usage() -> (u64, u64, u64) {
-    // Linux root directory
-    get_directory_usage(ROOT_DIR)
+/// Gets current power draw in microwatts, returns None if the device has no battery
+pub fn get_power_draw_microwatts() -> Option<u64> {
+    get_trimmed(Path::new(BATTERY_POWER_DRAW_DIR)).ok()?.parse::<u64>().ok()
+}
+
+/// Checks if a graphical session is available, on headless servers (E.g: through SSH) there is no
+/// screen to show
+pub fn has_display() -> bool {
+    std::env::var_os("DISPLAY").is_some() || std::env::var_os("WAYLAND_DISPLAY").is_some()
+}
+
+/// Gets the root disk as (mount point, total bytes, used bytes)
+pub fn get_disk_bytes() -> Option<(String, u64, u64)> {
+    get_directory_bytes(ROOT_DIR)
 }
 
 pub fn format_kernel_version() -> String {
-    format!("Linux {}", get_kernel_version())
+    format!("{} {}", KERNEL_NAME, get_kernel_version())
 }
 
 /// Gets gpu vendor and device ids and returns them as a tuple: (vendor, device)
 pub fn get_gpu_ids() -> Option<(String, String)> {
-    // On some systems the indexes might start at 1, so instead of iterating through every single
-    // possible "card*" we try the first two which are the most likely
-    let gpu_path = Path::new("/sys/class/drm/card0/device");
-    let fallback_gpu_path = Path::new("/sys/class/drm/card1/device");
+    let gpu_path = Path::new(GPU_DIR);
+    let fallback_gpu_path = Path::new(FALLBACK_GPU_DIR);
 
     let vendor = std::fs::read_to_string(gpu_path.join("vendor"))
         .or_else(|_| std::fs::read_to_string(fallback_gpu_path.join("vendor")))
@@ -76,8 +93,8 @@ pub fn get_gpu_ids() -> Option<(String, String)> {
 
 /// Gets subsystem IDs for the GPU, which are used to narrow down the possible names of the GPU
 fn get_gpu_subsystem_ids() -> Option<(String, String)> {
-    let gpu_path = Path::new("/sys/class/drm/card0/device");
-    let fallback_gpu_path = Path::new("/sys/class/drm/card1/device");
+    let gpu_path = Path::new(GPU_DIR);
+    let fallback_gpu_path = Path::new(FALLBACK_GPU_DIR);
 
     let subsystem_vendor = std::fs::read_to_string(gpu_path.join("subsystem_vendor"))
         .or_else(|_| std::fs::read_to_string(fallback_gpu_path.join("subsystem_vendor")))
@@ -96,16 +113,10 @@ fn get_gpu_subsystem_ids() -> Option<(String, String)> {
 }
 
 /// Gets GPU family and possible names, returns them as string
-pub fn get_gpu_name(cli: &Cli) -> Option<String> {
-    // TODO: This function is pretty long and, while being significantly faster than WGPU
-    // (45ms vs 3ms) it is also less accurate. Shorten it and add accuracy
+pub fn get_gpu_name() -> Option<String> {
     let (vendor_id, device_id) = get_gpu_ids()?;
-    let subsystem_ids = get_gpu_subsystem_ids();
-
-    let subsystem_ids = subsystem_ids
-        .map(|(subvendor, subdevice)| (format_hex(&subvendor), format_hex(&subdevice)));
 
-    if let Ok(cache) = get_cache(cli) {
+    if let Ok(cache) = get_cache() {
         // again, this shouldn't be collapsed
         if cache.gpu_device_id == device_id && cache.gpu_vendor_id == vendor_id {
             return Some(cache.gpu_name_pretty);
@@ -114,8 +125,39 @@ pub fn get_gpu_name(cli: &Cli) -> Option<String> {
         }
     }
 
-    let pci_ids = std::fs::read_to_string("/usr/share/hwdata/pci.ids")
-        .or_else(|_| std::fs::read_to_string("/usr/share/misc/pci.ids"))
+    get_gpu_name_from_pci_ids(&vendor_id, &device_id)
+}
+
+/// Gets the GPU name the same way as get_gpu_name() but never writes the cache, alongside where
+/// the name came from: "cache", "pci.ids" or "wgpu". wgpu is only probed if pci.ids has no match,
+/// just like a normal run would only store its name in the cache. Used by --bug-report
+pub fn find_gpu_name(cache: Option<&Cache>) -> Option<(String, &'static str)> {
+    let (vendor_id, device_id) = get_gpu_ids()?;
+
+    if let Some(cache) = cache
+        && cache.gpu_device_id == device_id
+        && cache.gpu_vendor_id == vendor_id
+    {
+        return Some((cache.gpu_name_pretty.clone(), "cache"));
+    }
+
+    get_gpu_name_from_pci_ids(&vendor_id, &device_id)
+        .map(|name| (name, "pci.ids"))
+        .or_else(|| get_gpu_name_pretty().map(|name| (name, "wgpu")))
+}
+
+/// Looks the GPU up in the pci.ids file, the subsystem name is preferred since it's the most
+/// precise one
+fn get_gpu_name_from_pci_ids(vendor_id: &str, device_id: &str) -> Option<String> {
+    // TODO: This function is pretty long and, while being significantly faster than WGPU
+    // (45ms vs 3ms) it is also less accurate. Shorten it and add accuracy
+    let subsystem_ids = get_gpu_subsystem_ids();
+
+    let subsystem_ids = subsystem_ids
+        .map(|(subvendor, subdevice)| (format_hex(&subvendor), format_hex(&subdevice)));
+
+    let pci_ids = std::fs::read_to_string(PCI_IDS_PATH)
+        .or_else(|_| std::fs::read_to_string(FALLBACK_PCI_IDS_PATH))
         .ok()?;
 
     let mut current_vendor = None;
@@ -131,7 +173,7 @@ pub fn get_gpu_name(cli: &Cli) -> Option<String> {
             current_device = None;
 
             if let Some(vendor) = line.split_whitespace().next() {
-                if vendor.eq_ignore_ascii_case(&vendor_id) {
+                if vendor.eq_ignore_ascii_case(vendor_id) {
                     current_vendor = Some(line.split_once("  ")?.1.trim());
                 } else {
                     current_vendor = None;
@@ -139,7 +181,7 @@ pub fn get_gpu_name(cli: &Cli) -> Option<String> {
             }
         } else if current_vendor.is_some() && line.starts_with('\t') && !line.starts_with("\t\t") {
             if let Some(device) = line.split_whitespace().next() {
-                if device.eq_ignore_ascii_case(&device_id) {
+                if device.eq_ignore_ascii_case(device_id) {
                     let name = line.split_once("  ")?.1.trim();
                     current_device = Some(name);
                 } else {
@@ -172,3 +214,36 @@ pub fn get_gpu_name(cli: &Cli) -> Option<String> {
 
     Some(format!("{} {}", current_vendor?, current_device?))
 }
+
+/// Lists where the Linux-only modules read their data from as (source, description) pairs, this is
+/// shown by --bug-report to know which files were used on the user's machine
+pub fn get_data_sources() -> Vec<(&'static str, String)> {
+    let gpu_ids = get_gpu_ids().map(|(vendor, device)| format!("{}:{}", vendor, device));
+    let gpu_subsystem_ids = get_gpu_subsystem_ids().map(|(subvendor, subdevice)| {
+        format!("{}:{}", format_hex(&subvendor), format_hex(&subdevice))
+    });
+
+    vec![
+        ("os-release", describe_paths(&[OS_RELEASE_PATH])),
+        ("GPU sysfs", describe_paths(&[GPU_DIR, FALLBACK_GPU_DIR])),
+        ("GPU ids", gpu_ids.unwrap_or_else(|| String::from("Unavailable"))),
+        (
+            "GPU subsystem ids",
+            gpu_subsystem_ids.unwrap_or_else(|| String::from("Unavailable")),
+        ),
+        ("pci.ids", describe_paths(&[PCI_IDS_PATH, FALLBACK_PCI_IDS_PATH])),
+        ("Battery capacity", describe_paths(&[BATTERY_CAPACITY_DIR])),
+        ("Battery status", describe_paths(&[BATTERY_STATUS_DIR])),
+        ("Battery power draw", describe_paths(&[BATTERY_POWER_DRAW_DIR])),
+    ]
+}
+
+/// Gets the first existing path out of a list of fallbacks, or "Not found" alongside every path
+/// that was tried
+fn describe_paths(paths: &[&str]) -> String {
+    paths
+        .iter()
+        .find(|path| Path::new(path).exists())
+        .map(|path| path.to_string())
+        .unwrap_or_else(|| format!("Not found ({})", paths.join(", ")))
+}
@@ -1,24 +1,35 @@
-use crate::sysinfo::*;
-use crate::cli::Cli;
+use crate::{cache::Cache, sysinfo::*};
+
+pub const KERNEL_NAME: &str = "MacOS";
 
 // Same as before
 pub fn get_power_draw() -> i32 {
     0
 }
+pub fn get_power_draw_microwatts() -> Option<u64> {
+    None
+}
+
+pub fn has_display() -> bool {
+    true
+}
 
-pub fn get_disk_usage() -> (u64, u64, u64) {
-    // MacOS root directory
-    get_directory_usage("/")
+pub fn get_disk_bytes() -> Option<(String, u64, u64)> {
+    get_directory_bytes("/")
 }
 
 pub fn format_kernel_version() -> String {
-    format!("MacOS {}", get_kernel_version())
+    format!("{} {}", KERNEL_NAME, get_kernel_version())
 }
 
 pub fn get_distro_id() -> String {
     String::from("macos")
 }
 
+pub fn get_distro_like_ids() -> Vec<String> {
+    Vec::new()
+}
+
 // the following functions will never run since they're for linux only features but the compiler
 // complains if they're not in macos
 pub fn get_battery() -> (String, String) {
@@ -30,6 +41,12 @@ pub fn get_gpu_ids() -> Option<(String, String)> {
 pub fn get_gpu_subsystem_ids() -> Option<(String, String)> {
     Some((String::from("Null"), String::from("Null")))
 }
-pub fn get_gpu_name(_cli: &Cli) -> Option<String> {
+pub fn get_gpu_name() -> Option<String> {
     Some(String::from("Null"))
 }
+pub fn find_gpu_name(_cache: Option<&Cache>) -> Option<(String, &'static str)> {
+    None
+}
+pub fn get_data_sources() -> Vec<(&'static str, String)> {
+    Vec::new()
+}
@@ -0,0 +1,262 @@
+//! Typed representation of every module rustfetch can display.
+//! Values are collected here once as raw numbers and strings, then rendered by the display
+//! functions or serialized as they are (E.g: --json). A module is None if it's disabled in the
+//! config or if it's not available on the current machine.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    common::{get_percentage_from_part, round_to_two_decimal},
+    config::Config,
+    platform,
+    sysinfo::*,
+};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SystemReport {
+    pub identifier: Option<IdentifierInfo>,
+    pub os: Option<OsInfo>,
+    pub kernel: Option<KernelInfo>,
+    pub cpu: Option<CpuInfo>,
+    pub gpu: Option<GpuInfo>,
+    pub screen: Option<ScreenInfo>,
+    pub ram: Option<MemoryInfo>,
+    pub swap: Option<MemoryInfo>,
+    pub uptime: Option<UptimeInfo>,
+    pub battery: Option<BatteryInfo>,
+    pub power_draw: Option<PowerDrawInfo>,
+    pub disk: Option<DiskInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdentifierInfo {
+    pub username: String,
+    pub host_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OsInfo {
+    pub name: String,
+    /// CPU architecture the program was compiled for
+    pub arch: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KernelInfo {
+    pub name: String,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CpuInfo {
+    pub name: String,
+    /// Only collected if cpu_frequency is on in the config
+    pub frequency_mhz: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuInfo {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenInfo {
+    pub resolution: Option<Resolution>,
+    pub refresh_rate_hz: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Resolution {
+    pub width: u64,
+    pub height: u64,
+}
+
+/// Shared by RAM and swap
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryInfo {
+    pub total_bytes: u64,
+    pub used_bytes: u64,
+    /// Floored usage percentage, 0 if total_bytes is 0
+    pub percent: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UptimeInfo {
+    pub seconds: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatteryInfo {
+    pub capacity_percent: u64,
+    /// Status as reported by the system, E.g: "Charging", "Discharging", "Full"
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerDrawInfo {
+    pub watts: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskInfo {
+    pub mount_point: String,
+    pub total_bytes: u64,
+    pub used_bytes: u64,
+    pub percent: u64,
+}
+
+impl SystemReport {
+    /// Flattens every available value of the report into (path, value) pairs, where path is made of
+    /// the same keys used by --json joined by dots. For example ("ram.used_bytes", 8053063680).
+    /// Unavailable modules and values are skipped
+    pub fn fields(&self) -> Vec<(String, serde_json::Value)> {
+        let mut fields = Vec::new();
+        if let Ok(value) = serde_json::to_value(self) {
+            flatten_value(String::new(), value, &mut fields);
+        }
+        fields
+    }
+}
+
+fn flatten_value(
+    path: String,
+    value: serde_json::Value,
+    fields: &mut Vec<(String, serde_json::Value)>,
+) {
+    match value {
+        serde_json::Value::Null => {},
+        serde_json::Value::Object(map) => {
+            for (key, value) in map {
+                let path = if path.is_empty() {
+                    key
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                flatten_value(path, value, fields);
+            }
+        },
+        value => fields.push((path, value)),
+    }
+}
+
+/// Collects every module that is turned on in the config into a SystemReport
+///
+/// This is the entry point for using rustfetch as a library, it does not print anything to stdout.
+/// The GPU module is the only one that touches the disk: it creates the cache if it's missing or
+/// refreshes it if its ids are stale, printing a notice on stderr when it does
+pub fn collect(config: &Config) -> SystemReport {
+    let display = &config.display;
+    let sys = create_system(config);
+
+    SystemReport {
+        identifier: display.identifier.then(collect_identifier),
+        os: display.os.then(collect_os),
+        kernel: display.kernel.then(collect_kernel),
+        cpu: display.cpu.then(|| collect_cpu(&sys, config)),
+        // GPU, battery and power draw are Linux only
+        gpu: (display.gpu && cfg!(target_os = "linux")).then(collect_gpu).flatten(),
+        screen: display.screen.then(|| collect_screen(config)).flatten(),
+        ram: display.ram.then(|| collect_memory(get_ram_bytes(&sys))),
+        swap: display.swap.then(|| collect_memory(get_swap_bytes(&sys))),
+        uptime: display.uptime.then(|| UptimeInfo {
+            seconds: get_uptime_seconds(),
+        }),
+        battery: (display.battery && cfg!(target_os = "linux")).then(collect_battery).flatten(),
+        power_draw: (display.power_draw && cfg!(target_os = "linux"))
+            .then(collect_power_draw)
+            .flatten(),
+        disk: display.disk.then(collect_disk).flatten(),
+    }
+}
+
+fn collect_identifier() -> IdentifierInfo {
+    IdentifierInfo {
+        username: get_username(),
+        host_name: get_host_name(),
+    }
+}
+
+fn collect_os() -> OsInfo {
+    OsInfo {
+        name: get_os_name(),
+        arch: std::env::consts::ARCH.to_string(),
+    }
+}
+
+fn collect_kernel() -> KernelInfo {
+    KernelInfo {
+        name: platform::KERNEL_NAME.to_string(),
+        version: get_kernel_version(),
+    }
+}
+
+fn collect_cpu(sys: &sysinfo::System, config: &Config) -> CpuInfo {
+    CpuInfo {
+        name: get_cpu_name(sys),
+        frequency_mhz: config.display.cpu_frequency.then(|| get_cpu_frequency(sys)),
+    }
+}
+
+fn collect_gpu() -> Option<GpuInfo> {
+    platform::get_gpu_name().map(|name| GpuInfo { name })
+}
+
+fn collect_screen(config: &Config) -> Option<ScreenInfo> {
+    let resolution = config
+        .display
+        .resolution
+        .then(get_screen_resolution)
+        .flatten()
+        .map(|(width, height)| Resolution { width, height });
+    let refresh_rate_hz = config.display.refresh_rate.then(get_screen_refresh_rate).flatten();
+
+    if resolution.is_none() && refresh_rate_hz.is_none() {
+        return None;
+    }
+
+    Some(ScreenInfo {
+        resolution,
+        refresh_rate_hz,
+    })
+}
+
+fn collect_memory((total_bytes, used_bytes): (u64, u64)) -> MemoryInfo {
+    MemoryInfo {
+        total_bytes,
+        used_bytes,
+        percent: get_percentage_from_part(used_bytes as f64, total_bytes as f64).unwrap_or(0),
+    }
+}
+
+fn collect_battery() -> Option<BatteryInfo> {
+    let (capacity, status) = platform::get_battery();
+    let capacity_percent = capacity.parse::<u64>().ok()?;
+    if status == "Unavailable" {
+        return None;
+    }
+
+    Some(BatteryInfo {
+        capacity_percent,
+        status,
+    })
+}
+
+fn collect_power_draw() -> Option<PowerDrawInfo> {
+    // Some devices report 0 while plugged in, which is treated as unavailable
+    platform::get_power_draw_microwatts()
+        .filter(|&microwatts| microwatts > 0)
+        .map(|microwatts| PowerDrawInfo {
+            watts: round_to_two_decimal(microwatts as f64 / 1_000_000.0),
+        })
+}
+
+fn collect_disk() -> Option<DiskInfo> {
+    let (mount_point, total_bytes, used_bytes) = platform::get_disk_bytes()?;
+
+    Some(DiskInfo {
+        mount_point,
+        total_bytes,
+        used_bytes,
+        percent: get_percentage_from_part(used_bytes as f64, total_bytes as f64).unwrap_or(0),
+    })
+}
@@ -3,11 +3,6 @@ use sysinfo::*;
 
 use crate::{common::*, config::Config};
 
-const BYTES_TO_GB: u64 = 1_000_000_000;
-const KIB_TO_MB: u64 = 1024;
-const SECONDS_TO_HOURS: u64 = 3600;
-const MINUTES_TO_HOURS: u64 = 60;
-
 /// Creates a System variable once and refreshes features according to what is on in the config file
 pub fn create_system(config: &Config) -> System {
     let mut sys = System::new();
@@ -22,69 +17,36 @@ pub fn create_system(config: &Config) -> System {
     sys
 }
 
-/// Gets RAM usage values and returns them as a formatted String alongside the usage percentage as
-/// unsigned int. Returns 0 on all values in case any error occurs
-pub fn get_ram_usage(sys: &System) -> (String, String, u64) {
-    let total_kib = (sys.total_memory() / KIB_TO_MB) as f64;
-    let used_kib = (sys.used_memory() / KIB_TO_MB) as f64;
-    let percentage = get_percentage_from_part(used_kib, total_kib).unwrap_or(0);
-
-    (
-        convert_to_bytes(total_kib).unwrap_or(String::from("0 KiB")),
-        convert_to_bytes(used_kib).unwrap_or(String::from("0 KiB")),
-        percentage,
-    )
+/// Gets RAM values in bytes and returns them as (total, used)
+pub fn get_ram_bytes(sys: &System) -> (u64, u64) {
+    (sys.total_memory(), sys.used_memory())
 }
 
-/// Gets swap usage values and returns them as a formatted String alongside the usage percentage as
-/// unsigned int. Returns 0 on all values in case any error occurs
-pub fn get_swap_usage(sys: &System) -> (String, String, u64) {
-    let total_kib = (sys.total_swap() / KIB_TO_MB) as f64;
-    let used_kib = (sys.used_swap() / KIB_TO_MB) as f64;
-    let percentage = get_percentage_from_part(used_kib, total_kib).unwrap_or(0);
-
-    (
-        convert_to_bytes(total_kib).unwrap_or(String::from("0 KiB")),
-        convert_to_bytes(used_kib).unwrap_or(String::from("0 KiB")),
-        percentage,
-    )
+/// Gets swap values in bytes and returns them as (total, used)
+pub fn get_swap_bytes(sys: &System) -> (u64, u64) {
+    (sys.total_swap(), sys.used_swap())
 }
 
-/// Gets system uptime in HHh MMm SSs format
-pub fn get_uptime() -> String {
-    let uptime_seconds = System::uptime();
-
-    let hours = uptime_seconds / SECONDS_TO_HOURS;
-    let minutes = (uptime_seconds % SECONDS_TO_HOURS) / MINUTES_TO_HOURS;
-    let seconds = uptime_seconds % MINUTES_TO_HOURS;
-
-    if hours < 1 {
-        format!("{:02}m {:02}s", minutes, seconds)
-    } else {
-        format!("{:02}h {:02}m {:02}s", hours, minutes, seconds)
-    }
+/// Gets system uptime in seconds
+pub fn get_uptime_seconds() -> u64 {
+    System::uptime()
 }
 
-/// Gets disk (root) usage and returns in GB and percentage (floored)
-pub fn get_directory_usage(directory: &str) -> (u64, u64, u64) {
+/// Gets the disk containing the given directory (or "/" as fallback) and returns it as
+/// (mount point, total bytes, used bytes)
+pub fn get_directory_bytes(directory: &str) -> Option<(String, u64, u64)> {
     let disks = Disks::new_with_refreshed_list();
 
-    let disk = match disks
+    let disk = disks
         .iter()
         .find(|disk| disk.mount_point().to_string_lossy() == directory)
-        .or_else(|| disks.iter().find(|disk| disk.mount_point() == std::path::Path::new("/")))
-    {
-        Some(disk) => disk,
-        _ => return (0, 0, 0),
-    };
+        .or_else(|| disks.iter().find(|disk| disk.mount_point() == std::path::Path::new("/")))?;
 
     let total = disk.total_space();
     let free = disk.available_space();
     let used = total.saturating_sub(free);
 
-    let percentage = get_percentage_from_part(used as f64, total as f64).unwrap_or(0);
-
-    (total / BYTES_TO_GB, used / BYTES_TO_GB, percentage)
+    Some((disk.mount_point().to_string_lossy().to_string(), total, used))
 }
 
 /// Gets os name on any given system
@@ -124,7 +86,7 @@ pub fn get_gpu_name_pretty() -> Option<String> {
 
     if let Some(adapter) = adapters.into_iter().next() {
         let gpu_name = adapter.get_info().name;
-        return Some(strip_gpu_name(&gpu_name))
+        return Some(strip_gpu_name(&gpu_name));
     }
 
     None
@@ -1,7 +1,6 @@
 //! Test the serialization, deserialization and re-extraction of values stored in cache.
 
-use clap::Parser;
-use rustfetch::{cache::*, cli::Cli, platform::get_gpu_name};
+use rustfetch::{cache::*, platform::get_gpu_name};
 
 fn get_incorrect_ids() -> Cache {
     Cache {
@@ -14,9 +13,7 @@ fn get_incorrect_ids() -> Cache {
 #[test]
 /// tests that the pretty_name is reextracted if the ids are incorrect
 fn test_no_pretty_name() -> Result<(), Box<dyn std::error::Error>> {
-    let cli = Cli::parse();
-
-    let cache = get_cache(&cli)?;
+    let cache = get_cache()?;
     let name_before = cache.gpu_name_pretty;
 
     let cache_path = get_cache_path();
@@ -25,9 +22,9 @@ fn test_no_pretty_name() -> Result<(), Box<dyn std::error::Error>> {
     std::fs::write(cache_path, toml_string)?;
 
     // using the get_gpu_name function should regenerate the name and ids if they do not match
-    get_gpu_name(&cli);
+    get_gpu_name();
 
-    let cache = get_cache(&cli)?;
+    let cache = get_cache()?;
     let name_after = cache.gpu_name_pretty;
 
     assert_eq!(name_before, name_after);
@@ -0,0 +1,119 @@
+//! Test the color depth detection and downgrades inside src/common/color_mode.rs
+
+use std::collections::HashMap;
+
+use colored::Color;
+use rustfetch::{cli::ColorChoice, common::*};
+
+fn fake_env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+    let vars: HashMap<String, String> =
+        vars.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect();
+    move |key| vars.get(key).cloned()
+}
+
+// resolve_color_depth tests
+
+#[test]
+fn resolve_color_depth_correct_input() {
+    let truecolor = fake_env(&[("TERM", "xterm-256color"), ("COLORTERM", "truecolor")]);
+    assert_eq!(resolve_color_depth(ColorChoice::Auto, true, truecolor), ColorDepth::TrueColor);
+
+    let xterm = fake_env(&[("TERM", "xterm-256color")]);
+    assert_eq!(resolve_color_depth(ColorChoice::Auto, true, xterm), ColorDepth::Ansi256);
+
+    // The Linux console only has 16 colors
+    let console = fake_env(&[("TERM", "linux")]);
+    assert_eq!(resolve_color_depth(ColorChoice::Auto, true, console), ColorDepth::Ansi16);
+
+    // Pipes and redirects get no colors
+    let xterm = fake_env(&[("TERM", "xterm-256color")]);
+    assert_eq!(resolve_color_depth(ColorChoice::Auto, false, xterm), ColorDepth::None);
+}
+
+#[test]
+fn resolve_color_depth_edge_cases() {
+    let no_color = fake_env(&[("TERM", "xterm-256color"), ("NO_COLOR", "1")]);
+    assert_eq!(resolve_color_depth(ColorChoice::Auto, true, no_color), ColorDepth::None);
+    // An empty NO_COLOR does not count
+    let no_color = fake_env(&[("TERM", "xterm-256color"), ("NO_COLOR", "")]);
+    assert_eq!(resolve_color_depth(ColorChoice::Auto, true, no_color), ColorDepth::Ansi256);
+
+    let dumb = fake_env(&[("TERM", "dumb")]);
+    assert_eq!(resolve_color_depth(ColorChoice::Auto, true, dumb), ColorDepth::None);
+
+    let force = fake_env(&[("TERM", "linux"), ("CLICOLOR_FORCE", "1")]);
+    assert_eq!(resolve_color_depth(ColorChoice::Auto, false, force), ColorDepth::Ansi16);
+
+    // --color wins over the environment
+    let no_color = fake_env(&[("TERM", "xterm-256color"), ("NO_COLOR", "1")]);
+    assert_eq!(resolve_color_depth(ColorChoice::Always, false, no_color), ColorDepth::Ansi256);
+    let truecolor = fake_env(&[("COLORTERM", "24bit")]);
+    assert_eq!(resolve_color_depth(ColorChoice::Never, true, truecolor), ColorDepth::None);
+    assert_eq!(resolve_color_depth(ColorChoice::Always, false, fake_env(&[])), ColorDepth::Ansi16);
+}
+
+// downgrade_color tests
+
+#[test]
+fn downgrade_color_correct_input() {
+    let orange = Color::TrueColor {
+        r: 233,
+        g: 84,
+        b: 32,
+    };
+    assert_eq!(downgrade_color(orange, ColorDepth::TrueColor), orange);
+    assert_eq!(downgrade_color(orange, ColorDepth::Ansi256), Color::AnsiColor(166));
+    assert_eq!(downgrade_color(orange, ColorDepth::Ansi16), Color::BrightRed);
+
+    // Named colors are shown by every terminal
+    assert_eq!(downgrade_color(Color::Blue, ColorDepth::Ansi16), Color::Blue);
+    assert_eq!(downgrade_color(Color::Blue, ColorDepth::Ansi256), Color::Blue);
+}
+
+#[test]
+fn downgrade_color_edge_cases() {
+    // 256 colors are only downgraded on 16 color terminals
+    assert_eq!(downgrade_color(Color::AnsiColor(166), ColorDepth::Ansi16), Color::Red);
+    assert_eq!(downgrade_color(Color::AnsiColor(166), ColorDepth::Ansi256), Color::AnsiColor(166));
+    assert_eq!(downgrade_color(Color::AnsiColor(250), ColorDepth::Ansi16), Color::White);
+    // The first 16 are the named colors themselves
+    assert_eq!(downgrade_color(Color::AnsiColor(4), ColorDepth::Ansi16), Color::Blue);
+    assert_eq!(downgrade_color(Color::AnsiColor(9), ColorDepth::Ansi16), Color::BrightRed);
+}
+
+#[test]
+fn get_256_color_rgb_correct_input() {
+    assert_eq!(get_256_color_rgb(196), (255, 0, 0));
+    assert_eq!(get_256_color_rgb(67), (95, 135, 175));
+    assert_eq!(get_256_color_rgb(232), (8, 8, 8));
+    assert_eq!(get_256_color_rgb(255), (238, 238, 238));
+    // Every cube and gray color comes back as itself
+    for index in 16 ..= 255 {
+        let (r, g, b) = get_256_color_rgb(index);
+        assert_eq!(get_closest_256_color(r, g, b), index);
+    }
+}
+
+#[test]
+fn get_closest_256_color_correct_input() {
+    assert_eq!(get_closest_256_color(0, 0, 0), 16);
+    assert_eq!(get_closest_256_color(255, 255, 255), 231);
+    assert_eq!(get_closest_256_color(255, 0, 0), 196);
+    assert_eq!(get_closest_256_color(95, 135, 175), 67);
+}
+
+#[test]
+fn get_closest_256_color_edge_cases() {
+    // Grays are closer on the gray ramp than inside the cube
+    assert_eq!(get_closest_256_color(128, 128, 128), 244);
+    assert_eq!(get_closest_256_color(8, 8, 8), 232);
+    assert_eq!(get_closest_256_color(240, 240, 240), 255);
+}
+
+#[test]
+fn get_closest_16_color_correct_input() {
+    assert_eq!(get_closest_16_color(0, 0, 0), Color::Black);
+    assert_eq!(get_closest_16_color(200, 10, 10), Color::Red);
+    assert_eq!(get_closest_16_color(128, 128, 128), Color::BrightBlack);
+    assert_eq!(get_closest_16_color(250, 250, 250), Color::BrightWhite);
+}
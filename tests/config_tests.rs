@@ -0,0 +1,221 @@
+//! Test all functions within src/config.rs that do not touch the config file
+
+use rustfetch::config::*;
+
+#[test]
+fn enable_only_correct_input() -> Result<(), String> {
+    let mut display = DisplayConfig::default();
+    display.enable_only(&[String::from("ram"), String::from("battery")])?;
+
+    assert!(display.ram);
+    assert!(display.battery);
+    assert!(!display.cpu);
+    assert!(!display.identifier);
+    // Sub-options are left as they are
+    assert!(display.resolution);
+
+    Ok(())
+}
+
+#[test]
+fn enable_only_invalid_input() {
+    let mut display = DisplayConfig::default();
+    let result = display.enable_only(&[String::from("ram"), String::from("cpu_usage")]);
+    assert_eq!(result, Err(String::from("Unknown module: cpu_usage")));
+
+    // The config is left untouched on errors
+    assert!(display.cpu);
+}
+
+#[test]
+fn config_without_new_sections_is_valid() -> Result<(), toml::de::Error> {
+    // Config files created by older versions only have [display], they must still be parsed
+    let content = toml::to_string(&DisplayConfig::default()).unwrap_or_default();
+    let config: Config = toml::from_str(&format!("[display]\n{}", content))?;
+
+    assert!(config.display.cpu);
+    assert!(!config.motd.ansi);
+
+    // Modules added later are off, E.g: colors
+    let content = content.replace("colors = false\n", "");
+    let config: Config = toml::from_str(&format!("[display]\n{}", content))?;
+    assert!(!config.display.colors);
+
+    Ok(())
+}
+
+#[test]
+fn get_checked_modules_correct_input() -> Result<(), toml::de::Error> {
+    let content = toml::to_string(&DisplayConfig::default()).unwrap_or_default();
+    let config: Config = toml::from_str(&format!(
+        "[display]\n{}\n[check]\nram = {{ warn = 70 }}\nswap = {{}}",
+        content
+    ))?;
+
+    assert_eq!(config.check.ram, CheckThresholds {
+        warn: Some(70),
+        crit: None,
+    });
+    // Missing modules keep their default thresholds
+    assert_eq!(config.check.disk, CheckThresholds::new(80, 95));
+    assert_eq!(config.check.get_checked_modules(), vec![
+        String::from("ram"),
+        String::from("disk"),
+        String::from("battery"),
+    ]);
+
+    Ok(())
+}
+
+#[test]
+fn layout_config_correct_input() -> Result<(), toml::de::Error> {
+    let content = toml::to_string(&DisplayConfig::default()).unwrap_or_default();
+    let config: Config = toml::from_str(&format!(
+        "[display]\n{}\n[layout]\nlogo_position = \"top\"",
+        content
+    ))?;
+
+    assert_eq!(config.layout.logo_position, LogoPosition::Top);
+    // Missing options keep their defaults
+    assert_eq!(config.layout.overflow, Overflow::Truncate);
+    assert_eq!(config.layout.logo_min_width, 60);
+
+    Ok(())
+}
+
+#[test]
+fn with_all_modules_keeps_sections() -> Result<(), toml::de::Error> {
+    let content = toml::to_string(&DisplayConfig::default()).unwrap_or_default();
+    let content = content.replace("cpu = true", "cpu = false");
+    let config: Config = toml::from_str(&format!(
+        "[display]\n{}\n[layout]\nlogo_position = \"top\"\n[motd]\nansi = true",
+        content
+    ))?;
+
+    // Only [display] is changed, every other section is left as it was
+    let config = config.with_all_modules();
+    assert!(config.display.cpu);
+    assert!(config.display.colors);
+    assert_eq!(config.layout.logo_position, LogoPosition::Top);
+    assert!(config.motd.ansi);
+
+    Ok(())
+}
+
+#[test]
+fn logo_config_correct_input() -> Result<(), toml::de::Error> {
+    let content = toml::to_string(&DisplayConfig::default()).unwrap_or_default();
+    let config: Config = toml::from_str(&format!(
+        "[display]\n{}\n[logo]\ndistro = \"arch\"\nfile = \"-\"",
+        content
+    ))?;
+
+    assert_eq!(config.logo.distro.as_deref(), Some("arch"));
+    assert_eq!(config.logo.file, Some(std::path::PathBuf::from("-")));
+    // size is optional
+    assert_eq!(config.logo.size, LogoSize::Normal);
+
+    let config: Config =
+        toml::from_str(&format!("[display]\n{}\n[logo]\nsize = \"auto\"", content))?;
+    assert_eq!(config.logo.size, LogoSize::Auto);
+
+    Ok(())
+}
+
+#[test]
+fn colors_config_correct_input() -> Result<(), toml::de::Error> {
+    let content = toml::to_string(&DisplayConfig::default()).unwrap_or_default();
+    let config: Config = toml::from_str(&format!(
+        "[display]\n{}\n[colors]\nstyle = \"custom\"\nglyph = \"▲\"",
+        content
+    ))?;
+
+    assert_eq!(config.colors.style, ColorStyle::Custom);
+    assert_eq!(config.colors.glyph, "▲");
+    // bright is optional
+    assert!(config.colors.bright);
+
+    Ok(())
+}
+
+#[test]
+fn theme_config_correct_input() -> Result<(), toml::de::Error> {
+    let content = toml::to_string(&DisplayConfig::default()).unwrap_or_default();
+    let config: Config = toml::from_str(&format!(
+        "[display]\n{}\n[theme]\npreset = \"arrows\"\nkey_color = \"#ff8800\"",
+        content
+    ))?;
+
+    assert_eq!(config.theme.preset, ThemePreset::Arrows);
+    assert_eq!(config.theme.key_color.as_deref(), Some("#ff8800"));
+    // Every other option is left to the preset
+    assert_eq!(config.theme.separator, None);
+    assert_eq!(config.theme.keys_follow_logo, None);
+
+    Ok(())
+}
+
+#[test]
+fn icons_config_correct_input() -> Result<(), toml::de::Error> {
+    let content = toml::to_string(&DisplayConfig::default()).unwrap_or_default();
+    let config: Config = toml::from_str(&format!(
+        "[display]\n{}\n[icons]\nset = \"nerd\"\nmode = \"replace\"\ncpu = \"🧠\"",
+        content
+    ))?;
+
+    assert_eq!(config.icons.set, IconSet::Nerd);
+    assert_eq!(config.icons.mode, IconMode::Replace);
+    assert_eq!(config.icons.cpu.as_deref(), Some("🧠"));
+    assert_eq!(config.icons.battery, None);
+
+    Ok(())
+}
+
+#[test]
+fn progress_bars_config_correct_input() -> Result<(), toml::de::Error> {
+    let content = toml::to_string(&DisplayConfig::default()).unwrap_or_default();
+    let config: Config = toml::from_str(&format!(
+        "[display]\n{}\n[progress_bars]\nmode = \"bar\"\nwidth = 20",
+        content
+    ))?;
+
+    assert_eq!(config.progress_bars.mode, ProgressBarMode::Bar);
+    assert_eq!(config.progress_bars.width, 20);
+    // Every module with a percentage gets a bar by default
+    assert_eq!(config.progress_bars.modules, vec!["ram", "swap", "disk", "battery"]);
+    assert_eq!(config.progress_bars.filled, "█");
+
+    Ok(())
+}
+
+#[test]
+fn thresholds_config_correct_input() -> Result<(), toml::de::Error> {
+    let content = toml::to_string(&DisplayConfig::default()).unwrap_or_default();
+    let config: Config = toml::from_str(&format!(
+        "[display]\n{}\n[thresholds]\nram = [{{ at = 0, color = \"green\" }}, {{ at = 85, \
+         color = \"#ffaa00\" }}]\n[[thresholds.disk]]\nat = 90\ncolor = \"red\"",
+        content
+    ))?;
+
+    assert_eq!(
+        config.thresholds.ram.map(|levels| levels.iter().map(|level| level.at).collect()),
+        Some(vec![0, 85])
+    );
+    assert_eq!(config.thresholds.disk, Some(vec![ThresholdLevelConfig {
+        at: 90,
+        color: String::from("red"),
+    }]));
+    // Modules that are not set keep the built-in thresholds
+    assert_eq!(config.thresholds.battery, None);
+
+    Ok(())
+}
+
+#[test]
+fn thresholds_config_invalid_input() {
+    let content = toml::to_string(&DisplayConfig::default()).unwrap_or_default();
+    // Every level needs both a percentage and a color
+    let result: Result<Config, toml::de::Error> =
+        toml::from_str(&format!("[display]\n{}\n[thresholds]\nram = [{{ at = 85 }}]", content));
+    assert!(result.is_err());
+}
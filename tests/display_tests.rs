@@ -0,0 +1,312 @@
+//! Test the formatting functions inside src/common/display.rs
+
+use rustfetch::{
+    common::*,
+    config::*,
+    report::{BatteryInfo, CpuInfo, DiskInfo, IdentifierInfo},
+};
+
+// display_colors tests
+
+#[test]
+fn display_colors_correct_input() {
+    colored::control::set_override(false);
+
+    // Blocks are made of background color, so only spaces are left without colors
+    let lines = display_colors(&ColorsConfig::default());
+    assert_eq!(lines, vec![" ".repeat(24); 2]);
+
+    let colors = ColorsConfig {
+        style: ColorStyle::Circles,
+        ..ColorsConfig::default()
+    };
+    assert_eq!(display_colors(&colors), vec![String::from("● ● ● ● ● ● ● ●"); 2]);
+}
+
+#[test]
+fn display_colors_edge_cases() {
+    colored::control::set_override(false);
+
+    // Without the bright colors there is a single row
+    let colors = ColorsConfig {
+        style: ColorStyle::Custom,
+        glyph: String::from("▲"),
+        bright: false,
+    };
+    assert_eq!(display_colors(&colors), vec![String::from("▲ ▲ ▲ ▲ ▲ ▲ ▲ ▲")]);
+
+    // Wide glyphs are measured like every other line
+    let colors = ColorsConfig {
+        style: ColorStyle::Custom,
+        glyph: String::from("██"),
+        bright: false,
+    };
+    assert_eq!(get_display_width(&display_colors(&colors)[0]), 23);
+}
+
+// Theme tests
+
+#[test]
+fn theme_correct_input() -> Result<(), String> {
+    colored::control::set_override(false);
+
+    let theme = Theme::new(&ThemeConfig::default(), None)?;
+    assert_eq!(theme, Theme::default());
+    assert_eq!(theme.format_line("OS", "Debian"), "OS: Debian");
+
+    // --theme wins over the preset inside the config
+    let theme_config = ThemeConfig {
+        preset: ThemePreset::Minimal,
+        ..ThemeConfig::default()
+    };
+    let theme = Theme::new(&theme_config, Some(ThemePreset::Arrows))?;
+    assert_eq!(theme.format_line("OS", "Debian"), "OS ➜ Debian");
+
+    // Options set inside [theme] override the preset
+    let theme_config = ThemeConfig {
+        preset: ThemePreset::Minimal,
+        key_color: Some(String::from("#ff8800")),
+        key_style: Some(vec![String::from("bold"), String::from("Italic")]),
+        separator: Some(String::from(" | ")),
+        ..ThemeConfig::default()
+    };
+    let theme = Theme::new(&theme_config, None)?;
+    assert_eq!(theme.key_color, Some(colored::Color::TrueColor { r: 255, g: 136, b: 0 }));
+    assert_eq!(theme.key_styles, vec![colored::Styles::Bold, colored::Styles::Italic]);
+    assert_eq!(theme.format_line("OS", "Debian"), "OS | Debian");
+    assert!(theme.underline.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn theme_edge_cases() -> Result<(), String> {
+    colored::control::set_override(false);
+
+    // Only themes following the logo take its color
+    let mut theme = Theme::default();
+    theme.follow_logo(Some(colored::Color::Red));
+    assert_eq!(theme.key_color, None);
+
+    let mut theme = Theme::from_preset(ThemePreset::Distro);
+    theme.follow_logo(Some(colored::Color::Red));
+    assert_eq!(theme.key_color, Some(colored::Color::Red));
+
+    // Logos without colors keep the key color from the config
+    let theme_config = ThemeConfig {
+        key_color: Some(String::from("blue")),
+        keys_follow_logo: Some(true),
+        ..ThemeConfig::default()
+    };
+    let mut theme = Theme::new(&theme_config, None)?;
+    theme.follow_logo(None);
+    assert_eq!(theme.key_color, Some(colored::Color::Blue));
+
+    let identifier = IdentifierInfo {
+        username: String::from("user"),
+        host_name: Some(String::from("host")),
+    };
+    assert_eq!(display_identifier(&identifier, &Theme::default()), vec![
+        String::from("user@host"),
+        String::from("---------")
+    ]);
+
+    // Wide underlines are repeated less, empty ones leave out the underline
+    let mut theme = Theme {
+        underline: String::from("━━"),
+        ..Theme::default()
+    };
+    assert_eq!(display_identifier(&identifier, &theme)[1], "━━━━━━━━");
+    theme.underline = String::new();
+    assert_eq!(display_identifier(&identifier, &theme), vec![String::from("user@host")]);
+
+    Ok(())
+}
+
+#[test]
+fn theme_invalid_input() {
+    let theme_config = ThemeConfig {
+        value_color: Some(String::from("blurple")),
+        ..ThemeConfig::default()
+    };
+    assert_eq!(Theme::new(&theme_config, None), Err(String::from("Unknown color: blurple")));
+
+    let theme_config = ThemeConfig {
+        key_style: Some(vec![String::from("blink")]),
+        ..ThemeConfig::default()
+    };
+    assert_eq!(Theme::new(&theme_config, None), Err(String::from("Unknown key style: blink")));
+}
+
+// Icons tests
+
+#[test]
+fn icons_correct_input() {
+    colored::control::set_override(false);
+
+    let icons = Icons::new(&IconsConfig::default(), Some(IconSet::Emoji));
+    assert_eq!(icons.label("cpu", "CPU"), "🧠 CPU");
+
+    let theme = Theme {
+        icons,
+        ..Theme::default()
+    };
+    let cpu = CpuInfo {
+        name: String::from("AMD Ryzen 5"),
+        frequency_mhz: None,
+    };
+    assert_eq!(display_cpu(&cpu, &theme), "🧠 CPU: AMD Ryzen 5");
+
+    // Custom icons win over the set, and work without one
+    let icons_config = IconsConfig {
+        mode: IconMode::Replace,
+        cpu: Some(String::from("C")),
+        ..IconsConfig::default()
+    };
+    let icons = Icons::new(&icons_config, None);
+    assert_eq!(icons.label("cpu", "CPU"), "C");
+    assert_eq!(icons.label("os", "OS"), "OS");
+    let icons = Icons::new(&icons_config, Some(IconSet::Nerd));
+    assert_eq!(icons.label("cpu", "CPU"), "C");
+    assert_eq!(icons.label("os", "OS"), "\u{f17c}");
+}
+
+#[test]
+fn icons_edge_cases() {
+    let emoji = Icons::new(&IconsConfig::default(), Some(IconSet::Emoji));
+    assert_eq!(emoji.get_battery_icon(80, "Discharging"), Some("🔋"));
+    assert_eq!(emoji.get_battery_icon(10, "Discharging"), Some("🪫"));
+    assert_eq!(emoji.get_battery_icon(10, "Charging"), Some("🔌"));
+
+    // Nerd Font batteries fill up every 10%
+    let nerd = Icons::new(&IconsConfig::default(), Some(IconSet::Nerd));
+    assert_eq!(nerd.get_battery_icon(0, "Discharging"), Some("\u{f008e}"));
+    assert_eq!(nerd.get_battery_icon(55, "Discharging"), Some("\u{f007e}"));
+    assert_eq!(nerd.get_battery_icon(100, "Full"), Some("\u{f0079}"));
+    assert_eq!(nerd.get_battery_icon(150, "Full"), Some("\u{f0079}"));
+    assert_eq!(nerd.get_battery_icon(55, "charging"), Some("\u{f0084}"));
+    assert_eq!(nerd.battery_label(55, "Charging", "Battery"), "\u{f0084} Battery");
+
+    let icons_config = IconsConfig {
+        battery: Some(String::from("B")),
+        ..IconsConfig::default()
+    };
+    let icons = Icons::new(&icons_config, Some(IconSet::Emoji));
+    assert_eq!(icons.get_battery_icon(10, "Charging"), Some("B"));
+
+    assert_eq!(Icons::default().get_battery_icon(10, "Charging"), None);
+    assert_eq!(emoji.label("identifier", "user"), "user");
+
+    // Built-in emojis are always two columns wide, so the alignment holds
+    let modules = [
+        "os", "kernel", "uptime", "cpu", "gpu", "screen", "ram", "swap", "disk", "battery",
+        "power_draw",
+    ];
+    for module in modules {
+        let icon = get_builtin_icon(IconSet::Emoji, module).unwrap_or_default();
+        assert_eq!(get_display_width(icon), 2, "{} is not two columns wide", icon);
+    }
+    assert_eq!(get_display_width("🪫"), 2);
+    assert_eq!(get_display_width("🔌"), 2);
+}
+
+// Progress bar tests
+
+fn get_progress_bars(mode: ProgressBarMode) -> Result<ProgressBars, String> {
+    ProgressBars::new(&ProgressBarsConfig {
+        mode,
+        ..ProgressBarsConfig::default()
+    })
+}
+
+#[test]
+fn progress_bars_correct_input() -> Result<(), String> {
+    colored::control::set_override(false);
+
+    let bars = get_progress_bars(ProgressBarMode::Both)?;
+    assert_eq!(bars.render(61, colored::Color::Green), "[██████░░░░]");
+
+    let disk = DiskInfo {
+        mount_point: String::from("/"),
+        total_bytes: 200 * BYTES_IN_GB,
+        used_bytes: 122 * BYTES_IN_GB,
+        percent: 61,
+    };
+    let theme = Theme {
+        progress_bars: bars,
+        ..Theme::default()
+    };
+    assert_eq!(display_disk_usage(&disk, &theme), "Disk (/): 122GB / 200GB [██████░░░░] 61%");
+
+    let theme = Theme {
+        progress_bars: get_progress_bars(ProgressBarMode::Bar)?,
+        ..Theme::default()
+    };
+    assert_eq!(display_disk_usage(&disk, &theme), "Disk (/): [██████░░░░] 61%");
+
+    // Batteries keep their status next to the bar, unless only the bar is shown
+    let battery = BatteryInfo {
+        capacity_percent: 80,
+        status: String::from("Discharging"),
+    };
+    assert_eq!(display_battery(&battery, &theme), "Battery: [████████░░] 80%");
+    let theme = Theme {
+        progress_bars: get_progress_bars(ProgressBarMode::Both)?,
+        ..Theme::default()
+    };
+    assert_eq!(display_battery(&battery, &theme), "Battery: [████████░░] 80% (Discharging)");
+
+    // Off by default
+    assert_eq!(display_disk_usage(&disk, &Theme::default()), "Disk (/): 122GB / 200GB (61%)");
+
+    Ok(())
+}
+
+#[test]
+fn progress_bars_edge_cases() -> Result<(), String> {
+    colored::control::set_override(false);
+
+    let bars = get_progress_bars(ProgressBarMode::Both)?;
+    assert_eq!(bars.render(0, colored::Color::Green), "[░░░░░░░░░░]");
+    assert_eq!(bars.render(4, colored::Color::Green), "[░░░░░░░░░░]");
+    assert_eq!(bars.render(5, colored::Color::Green), "[█░░░░░░░░░]");
+    // Percentages above 100 fill the bar without overflowing it
+    assert_eq!(bars.render(150, colored::Color::Red), "[██████████]");
+
+    let bars = ProgressBars::new(&ProgressBarsConfig {
+        mode: ProgressBarMode::Both,
+        modules: vec![String::from("ram")],
+        width: 4,
+        filled: String::from("#"),
+        empty: String::from("-"),
+        filled_color: Some(String::from("blue")),
+        empty_color: Some(String::from("#333333")),
+    })?;
+    assert_eq!(bars.render(50, colored::Color::Green), "[##--]");
+    assert_eq!(bars.get_mode("ram"), ProgressBarMode::Both);
+    // Modules that are not listed never get a bar
+    assert_eq!(bars.get_mode("disk"), ProgressBarMode::None);
+
+    let bars = ProgressBars::new(&ProgressBarsConfig {
+        width: 0,
+        ..ProgressBarsConfig::default()
+    })?;
+    assert_eq!(bars.render(50, colored::Color::Green), "[]");
+
+    Ok(())
+}
+
+#[test]
+fn progress_bars_invalid_input() {
+    let result = ProgressBars::new(&ProgressBarsConfig {
+        modules: vec![String::from("cpu")],
+        ..ProgressBarsConfig::default()
+    });
+    assert_eq!(result, Err(String::from("Module without a percentage in progress_bars: cpu")));
+
+    let result = ProgressBars::new(&ProgressBarsConfig {
+        filled_color: Some(String::from("blurple")),
+        ..ProgressBarsConfig::default()
+    });
+    assert_eq!(result, Err(String::from("Unknown color: blurple")));
+}
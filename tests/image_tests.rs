@@ -0,0 +1,258 @@
+//! Test the image logos inside src/common/image.rs
+
+use std::collections::HashMap;
+
+use rustfetch::{
+    common::*,
+    config::{ImageProtocol, LogoPosition},
+};
+
+/// Encodes pixels into an in-memory PNG, bytes are laid out according to color_type
+fn encode_png(width: u32, height: u32, color_type: png::ColorType, bytes: &[u8]) -> Vec<u8> {
+    let mut data = Vec::new();
+    let mut encoder = png::Encoder::new(&mut data, width, height);
+    encoder.set_color(color_type);
+    encoder.set_depth(png::BitDepth::Eight);
+    if let Ok(mut writer) = encoder.write_header() {
+        writer.write_image_data(bytes).unwrap_or_default();
+    }
+    data
+}
+
+fn fake_env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+    let vars: HashMap<String, String> =
+        vars.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect();
+    move |key| vars.get(key).cloned()
+}
+
+// decode_png tests
+
+#[test]
+fn decode_png_correct_input() -> Result<(), String> {
+    let data = encode_png(2, 1, png::ColorType::Rgb, &[255, 0, 0, 0, 0, 255]);
+    let image = decode_png(data.clone())?;
+
+    assert_eq!((image.width, image.height), (2, 1));
+    assert_eq!(image.pixels, vec![[255, 0, 0, 255], [0, 0, 255, 255]]);
+    // Kitty gets the file as it is
+    assert_eq!(image.data, data);
+
+    let data = encode_png(1, 1, png::ColorType::GrayscaleAlpha, &[128, 0]);
+    assert_eq!(decode_png(data)?.pixels, vec![[128, 128, 128, 0]]);
+
+    Ok(())
+}
+
+#[test]
+fn decode_png_invalid_input() {
+    assert!(decode_png(Vec::new()).is_err());
+    assert!(decode_png(b"not a png".to_vec()).is_err());
+    assert!(load_png(std::path::Path::new("/nonexistent/logo.png")).is_err());
+}
+
+// resolve_image_protocol tests
+
+#[test]
+fn resolve_image_protocol_correct_input() {
+    let kitty = fake_env(&[("TERM", "xterm-kitty")]);
+    assert_eq!(resolve_image_protocol(ImageProtocol::Auto, kitty), ImageProtocol::Kitty);
+
+    let wezterm = fake_env(&[("TERM", "xterm-256color"), ("TERM_PROGRAM", "WezTerm")]);
+    assert_eq!(resolve_image_protocol(ImageProtocol::Auto, wezterm), ImageProtocol::Kitty);
+
+    let foot = fake_env(&[("TERM", "foot")]);
+    assert_eq!(resolve_image_protocol(ImageProtocol::Auto, foot), ImageProtocol::Sixel);
+
+    let mlterm = fake_env(&[("TERM", "xterm"), ("MLTERM", "3.9.3")]);
+    assert_eq!(resolve_image_protocol(ImageProtocol::Auto, mlterm), ImageProtocol::Sixel);
+}
+
+#[test]
+fn resolve_image_protocol_edge_cases() {
+    // Unknown terminals get half-blocks, which work everywhere
+    let xterm = fake_env(&[("TERM", "xterm-256color")]);
+    assert_eq!(resolve_image_protocol(ImageProtocol::Auto, xterm), ImageProtocol::HalfBlocks);
+    let empty = fake_env(&[]);
+    assert_eq!(resolve_image_protocol(ImageProtocol::Auto, empty), ImageProtocol::HalfBlocks);
+
+    // A protocol from the config or the CLI is trusted as it is
+    let xterm = fake_env(&[("TERM", "xterm-256color")]);
+    assert_eq!(resolve_image_protocol(ImageProtocol::Sixel, xterm), ImageProtocol::Sixel);
+    let kitty = fake_env(&[("TERM", "xterm-kitty")]);
+    assert_eq!(resolve_image_protocol(ImageProtocol::HalfBlocks, kitty), ImageProtocol::HalfBlocks);
+}
+
+// get_image_rows tests
+
+#[test]
+fn get_image_rows_correct_input() {
+    // Cells are twice as tall as they are wide
+    assert_eq!(get_image_rows(100, 100, 30), 15);
+    assert_eq!(get_image_rows(200, 100, 30), 8);
+    assert_eq!(get_image_rows(100, 200, 10), 10);
+}
+
+#[test]
+fn get_image_rows_edge_cases() {
+    assert_eq!(get_image_rows(1000, 1, 10), 1);
+    assert_eq!(get_image_rows(0, 100, 10), 1);
+}
+
+// encoder tests
+
+#[test]
+fn encode_kitty_correct_input() {
+    assert_eq!(encode_kitty(b"abc", 3, 2), "\x1b_Ga=T,f=100,q=2,C=1,c=3,r=2,m=0;YWJj\x1b\\");
+
+    // 6000 bytes are 8000 base64 characters, so two chunks
+    let escape = encode_kitty(&[0; 6000], 30, 15);
+    assert!(escape.starts_with("\x1b_Ga=T,f=100,q=2,C=1,c=30,r=15,m=1;"));
+    assert_eq!(escape.matches("\x1b_G").count(), 2);
+    assert!(escape.contains("\x1b\\\x1b_Gm=0;"));
+}
+
+#[test]
+fn encode_sixel_correct_input() -> Result<(), String> {
+    // A red pixel on one cell is scaled to 10x10, so a full band of 6 rows and one of 4
+    let image = decode_png(encode_png(1, 1, png::ColorType::Rgb, &[255, 0, 0]))?;
+    assert_eq!(
+        encode_sixel(&image, 1, 1),
+        "\x1bP0;1;0q\"1;1;10;10#180;2;100;0;0#180!10~$-#180!10N$-\x1b\\"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn encode_sixel_edge_cases() -> Result<(), String> {
+    // Transparent pixels are never drawn, so there are no colors at all
+    let image = decode_png(encode_png(1, 1, png::ColorType::Rgba, &[255, 0, 0, 0]))?;
+    assert_eq!(encode_sixel(&image, 1, 1), "\x1bP0;1;0q\"1;1;10;10--\x1b\\");
+
+    // Two colors in the same band each get their own pass, the image is 10x5 so a band of 5 rows
+    let image = decode_png(encode_png(2, 1, png::ColorType::Rgb, &[255, 0, 0, 0, 0, 255]))?;
+    let escape = encode_sixel(&image, 1, 1);
+    assert!(escape.ends_with("#5!5?!5^$#180!5^$-\x1b\\"));
+
+    Ok(())
+}
+
+// scale_image and render_half_blocks tests
+
+#[test]
+fn scale_image_correct_input() -> Result<(), String> {
+    let image = decode_png(encode_png(1, 1, png::ColorType::Rgb, &[10, 20, 30]))?;
+    assert_eq!(scale_image(&image, 2, 2), vec![[10, 20, 30, 255]; 4]);
+
+    // Transparent pixels lower the alpha but do not darken the color
+    let bytes = [255, 0, 0, 255, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 255, 255];
+    let image = decode_png(encode_png(2, 2, png::ColorType::Rgba, &bytes))?;
+    assert_eq!(scale_image(&image, 1, 1), vec![[127, 0, 127, 127]]);
+
+    Ok(())
+}
+
+#[test]
+fn scale_image_edge_cases() -> Result<(), String> {
+    let image = decode_png(encode_png(1, 1, png::ColorType::Rgb, &[10, 20, 30]))?;
+    assert!(scale_image(&image, 0, 0).is_empty());
+
+    let image = decode_png(encode_png(1, 1, png::ColorType::Rgba, &[10, 20, 30, 0]))?;
+    assert_eq!(scale_image(&image, 1, 1), vec![[0, 0, 0, 0]]);
+
+    Ok(())
+}
+
+#[test]
+fn render_half_blocks_correct_input() -> Result<(), String> {
+    colored::control::set_override(false);
+
+    // Every character is two pixels tall, so a square image is half as many lines as columns
+    let image = decode_png(encode_png(1, 1, png::ColorType::Rgb, &[255, 0, 0]))?;
+    assert_eq!(render_half_blocks(&image, 4), vec![String::from("▀▀▀▀"); 2]);
+
+    // The top pixel is transparent, the bottom one is not
+    let image = decode_png(encode_png(1, 2, png::ColorType::Rgba, &[0, 0, 0, 0, 255, 0, 0, 255]))?;
+    assert_eq!(render_half_blocks(&image, 1), vec![String::from("▄")]);
+
+    Ok(())
+}
+
+#[test]
+fn render_half_blocks_edge_cases() -> Result<(), String> {
+    colored::control::set_override(false);
+
+    // Odd heights leave the bottom half of the last line empty
+    let image = decode_png(encode_png(1, 1, png::ColorType::Rgb, &[255, 0, 0]))?;
+    assert_eq!(render_half_blocks(&image, 1), vec![String::from("▀")]);
+
+    let image = decode_png(encode_png(1, 1, png::ColorType::Rgba, &[255, 0, 0, 0]))?;
+    assert_eq!(render_half_blocks(&image, 2), vec![String::from("  ")]);
+
+    // Very wide images still get a line
+    let image = decode_png(encode_png(8, 1, png::ColorType::Grayscale, &[255; 8]))?;
+    assert_eq!(render_half_blocks(&image, 2).len(), 1);
+
+    Ok(())
+}
+
+// write_image_logo tests
+
+fn get_rendered_image(layout: &Layout) -> Result<(String, String), Box<dyn std::error::Error>> {
+    let image = ImageLogo {
+        columns: 3,
+        rows: 2,
+        escape: String::from("IMAGE"),
+    };
+    let info_lines = vec![String::from("OS: Fedora"), String::from("CPU: Ryzen")];
+
+    let mut rendered = Vec::new();
+    write_image_logo(&mut rendered, &image, info_lines.clone(), layout)?;
+
+    // The info goes next to blank lines as large as the image
+    let mut text = Vec::new();
+    write_logo(&mut text, vec![String::from("   "); 2], info_lines, "", layout)?;
+
+    Ok((String::from_utf8(rendered)?, String::from_utf8(text)?))
+}
+
+#[test]
+fn write_image_logo_correct_input() -> Result<(), Box<dyn std::error::Error>> {
+    colored::control::set_override(false);
+
+    let (rendered, text) = get_rendered_image(&Layout::default())?;
+    assert_eq!(rendered, format!("\n\n\x1b[2A\x1b7IMAGE\x1b8{}", text));
+
+    // On the right the image starts after the widest info line, the padding and two spaces
+    let layout = Layout {
+        logo_position: LogoPosition::Right,
+        ..Layout::default()
+    };
+    let (rendered, text) = get_rendered_image(&layout)?;
+    assert_eq!(rendered, format!("\n\n\x1b[2A\x1b7\x1b[13CIMAGE\x1b8{}", text));
+
+    Ok(())
+}
+
+#[test]
+fn write_image_logo_edge_cases() -> Result<(), Box<dyn std::error::Error>> {
+    colored::control::set_override(false);
+
+    // No image is drawn when the logo would be hidden
+    let layout = Layout {
+        logo_position: LogoPosition::None,
+        ..Layout::default()
+    };
+    let (rendered, text) = get_rendered_image(&layout)?;
+    assert_eq!(rendered, text);
+
+    let layout = Layout {
+        width: Some(20),
+        logo_min_width: 60,
+        ..Layout::default()
+    };
+    let (rendered, text) = get_rendered_image(&layout)?;
+    assert_eq!(rendered, text);
+
+    Ok(())
+}
@@ -0,0 +1,345 @@
+//! Test the logo markup parser inside src/common/logo.rs
+
+use rustfetch::{
+    common::*,
+    config::{LogoPosition, LogoSize, Overflow},
+};
+
+fn segment(text: &str, color_index: usize) -> LogoSegment {
+    LogoSegment {
+        text: String::from(text),
+        color_index,
+    }
+}
+
+// parse_logo_line tests
+
+#[test]
+fn parse_logo_line_correct_input() {
+    let mut color_index = 0;
+    let result = parse_logo_line("$1//$2osss$3+", &mut color_index);
+    assert_eq!(result, vec![segment("//", 0), segment("osss", 1), segment("+", 2)]);
+    // The last color is carried over to the next line
+    assert_eq!(color_index, 2);
+}
+
+#[test]
+fn parse_logo_line_edge_cases() {
+    // Dollar signs that are not markers are kept
+    let mut color_index = 0;
+    let result = parse_logo_line("$$ $0 $", &mut color_index);
+    assert_eq!(result, vec![segment("$$ $0 $", 0)]);
+
+    let result = parse_logo_line("", &mut color_index);
+    assert!(result.is_empty());
+
+    // Consecutive markers do not produce empty segments
+    let result = parse_logo_line("$1$2o", &mut color_index);
+    assert_eq!(result, vec![segment("o", 1)]);
+}
+
+#[test]
+fn parse_logo_carries_color_across_lines() {
+    let lines = vec![String::from("$2ab"), String::from("cd$1e")];
+    let result = parse_logo(&lines);
+    assert_eq!(result, vec![vec![segment("ab", 1)], vec![
+        segment("cd", 1),
+        segment("e", 0)
+    ]]);
+}
+
+// Logo width tests
+
+#[test]
+fn get_logo_line_width_ignores_markers() {
+    let mut color_index = 0;
+    let segments = parse_logo_line("  $2| _____ $1|", &mut color_index);
+    assert_eq!(get_logo_line_width(&segments), 11);
+}
+
+#[test]
+fn get_logo_line_width_edge_cases() {
+    // Multibyte characters take up a single column, wide characters take up two
+    let mut color_index = 0;
+    let segments = parse_logo_line("$1╭─$2⣿╮", &mut color_index);
+    assert_eq!(get_logo_line_width(&segments), 4);
+
+    let segments = parse_logo_line("$1日本", &mut color_index);
+    assert_eq!(get_logo_line_width(&segments), 4);
+}
+
+#[test]
+fn every_logo_fits_its_palette() {
+    // A marker beyond the palette would silently fall back to the first color
+    let sizes = [LogoSize::Small, LogoSize::Normal, LogoSize::Large];
+    for (distro_id, size) in LOGO_IDS.iter().flat_map(|id| sizes.map(|size| (id, size))) {
+        let logo = parse_logo(&get_logo_lines(distro_id, size));
+        assert!(!logo.is_empty(), "{} has no logo", distro_id);
+
+        let palette_size = get_logo_palette(distro_id).len();
+        for segment in logo.iter().flatten() {
+            assert!(
+                segment.color_index < palette_size,
+                "{} uses color ${} but its palette has {} colors",
+                distro_id,
+                segment.color_index + 1,
+                palette_size
+            );
+        }
+    }
+}
+
+#[test]
+fn colorize_logo_line_strips_markers() {
+    colored::control::set_override(false);
+    let mut color_index = 0;
+    let segments = parse_logo_line("$1//$2oss", &mut color_index);
+    let result = colorize_logo_line(&segments, &get_logo_palette("endeavouros"));
+    assert_eq!(result, String::from("//oss"));
+}
+
+// Layout tests
+
+fn get_rendered_logo(layout: &Layout) -> Result<String, Box<dyn std::error::Error>> {
+    let logo_lines = vec![String::from("$1/\\"), String::from("$1\\/")];
+    let info_lines = vec![
+        String::from("OS: Fedora Linux"),
+        String::from("CPU: AMD Ryzen 5 5600X"),
+    ];
+
+    let mut rendered = Vec::new();
+    write_logo(&mut rendered, logo_lines, info_lines, "fedora", layout)?;
+    Ok(String::from_utf8(rendered)?)
+}
+
+#[test]
+fn write_logo_without_width() -> Result<(), Box<dyn std::error::Error>> {
+    colored::control::set_override(false);
+    // Piped output keeps full values
+    let result = get_rendered_logo(&Layout::default())?;
+    assert_eq!(result, "/\\   OS: Fedora Linux\n\\/   CPU: AMD Ryzen 5 5600X\n");
+
+    Ok(())
+}
+
+#[test]
+fn write_logo_narrow_width() -> Result<(), Box<dyn std::error::Error>> {
+    colored::control::set_override(false);
+    let layout = Layout {
+        width: Some(15),
+        ..Default::default()
+    };
+    let result = get_rendered_logo(&layout)?;
+    // The info column is 10 columns wide, after the logo, the padding and two spaces
+    assert_eq!(result, "/\\   OS: Fedor…\n\\/   CPU: AMD …\n");
+
+    let layout = Layout {
+        width: Some(15),
+        overflow: Overflow::Wrap,
+        ..Default::default()
+    };
+    let result = get_rendered_logo(&layout)?;
+    let expected = [
+        "/\\   OS: Fedora",
+        "\\/       Linux",
+        "     CPU: AMD",
+        "          Ryzen",
+        "          5",
+        "          5600X",
+    ];
+    assert_eq!(result, format!("{}\n", expected.join("\n")));
+
+    Ok(())
+}
+
+#[test]
+fn write_logo_wraps_after_theme_separator() -> Result<(), Box<dyn std::error::Error>> {
+    colored::control::set_override(false);
+    let logo_lines = vec![String::from("$1/\\")];
+    let info_lines = vec![String::from("CPU ➜ AMD Ryzen 5")];
+    let layout = Layout {
+        width: Some(17),
+        overflow: Overflow::Wrap,
+        separator: String::from(" ➜ "),
+        ..Default::default()
+    };
+
+    let mut rendered = Vec::new();
+    write_logo(&mut rendered, logo_lines, info_lines, "fedora", &layout)?;
+    // Wrapped parts line up with the value, after the arrow
+    let expected = ["/\\   CPU ➜ AMD", "           Ryzen", "           5"];
+    assert_eq!(String::from_utf8(rendered)?, format!("{}\n", expected.join("\n")));
+
+    Ok(())
+}
+
+#[test]
+fn write_logo_aligns_wide_icons() -> Result<(), Box<dyn std::error::Error>> {
+    colored::control::set_override(false);
+    let logo_lines = vec![String::from("$1/\\"), String::from("$1\\/")];
+    let info_lines = vec![String::from("🐧 OS: Fedora"), String::from("🧠 CPU: AMD")];
+    let layout = Layout {
+        logo_position: LogoPosition::Right,
+        ..Default::default()
+    };
+
+    let mut rendered = Vec::new();
+    write_logo(&mut rendered, logo_lines, info_lines, "fedora", &layout)?;
+    // Emojis are two columns wide, so the logo still starts on the same column
+    assert_eq!(String::from_utf8(rendered)?, "🐧 OS: Fedora   /\\\n🧠 CPU: AMD     \\/\n");
+
+    Ok(())
+}
+
+#[test]
+fn write_logo_hides_logo_below_min_width() -> Result<(), Box<dyn std::error::Error>> {
+    colored::control::set_override(false);
+    let layout = Layout {
+        width: Some(30),
+        logo_min_width: 40,
+        ..Default::default()
+    };
+    let result = get_rendered_logo(&layout)?;
+    assert_eq!(result, "OS: Fedora Linux\nCPU: AMD Ryzen 5 5600X\n");
+
+    Ok(())
+}
+
+#[test]
+fn write_logo_positions() -> Result<(), Box<dyn std::error::Error>> {
+    colored::control::set_override(false);
+    let layout = Layout {
+        logo_position: LogoPosition::Right,
+        ..Default::default()
+    };
+    let result = get_rendered_logo(&layout)?;
+    assert_eq!(result, "OS: Fedora Linux         /\\\nCPU: AMD Ryzen 5 5600X   \\/\n");
+
+    // Padding is vertical when the logo is on top
+    let layout = Layout {
+        logo_position: LogoPosition::Top,
+        padding: 2,
+        ..Default::default()
+    };
+    let result = get_rendered_logo(&layout)?;
+    assert_eq!(result, "/\\\n\\/\n\n\nOS: Fedora Linux\nCPU: AMD Ryzen 5 5600X\n");
+
+    let layout = Layout {
+        logo_position: LogoPosition::None,
+        ..Default::default()
+    };
+    let result = get_rendered_logo(&layout)?;
+    assert_eq!(result, "OS: Fedora Linux\nCPU: AMD Ryzen 5 5600X\n");
+
+    Ok(())
+}
+
+#[test]
+fn write_logo_top_ignores_min_width() -> Result<(), Box<dyn std::error::Error>> {
+    colored::control::set_override(false);
+    // The logo on top only needs to fit by itself
+    let layout = Layout {
+        logo_position: LogoPosition::Top,
+        width: Some(10),
+        logo_min_width: 60,
+        padding: 0,
+        ..Default::default()
+    };
+    let result = get_rendered_logo(&layout)?;
+    assert_eq!(result, "/\\\n\\/\nOS: Fedor…\nCPU: AMD …\n");
+
+    Ok(())
+}
+
+// read_logo_file tests
+
+#[test]
+fn read_logo_file_correct_input() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::temp_dir().join(format!("rustfetch_logo_{}.txt", std::process::id()));
+    std::fs::write(&path, "$1/\\\r\n$2\\/\n")?;
+
+    let result = read_logo_file(&path);
+    std::fs::remove_file(&path)?;
+    // Windows line endings are handled as well
+    assert_eq!(result, Ok(vec![String::from("$1/\\"), String::from("$2\\/")]));
+
+    Ok(())
+}
+
+#[test]
+fn read_logo_file_invalid_input() {
+    let result = read_logo_file(std::path::Path::new("/nonexistent_rustfetch_dir/logo.txt"));
+    assert!(result.is_err());
+}
+
+// resolve_logo_id tests
+
+#[test]
+fn resolve_logo_id_correct_input() {
+    assert_eq!(resolve_logo_id("fedora", &[]), String::from("fedora"));
+
+    // Kubuntu is based on Ubuntu, which is based on Debian
+    let like_ids = vec![String::from("ubuntu"), String::from("debian")];
+    assert_eq!(resolve_logo_id("kubuntu", &like_ids), String::from("ubuntu"));
+
+    // The first id with a logo wins, even if it is not the first one
+    let like_ids = vec![String::from("rhel"), String::from("fedora")];
+    assert_eq!(resolve_logo_id("rocky", &like_ids), String::from("fedora"));
+}
+
+#[test]
+fn resolve_logo_id_edge_cases() {
+    // Unknown distros get the generic Tux logo
+    assert_eq!(resolve_logo_id("unknown", &[]), String::from(FALLBACK_LOGO_ID));
+    let like_ids = vec![String::from("rhel")];
+    assert_eq!(resolve_logo_id("almalinux", &like_ids), String::from(FALLBACK_LOGO_ID));
+    assert!(!get_logo_lines(FALLBACK_LOGO_ID, LogoSize::Normal).is_empty());
+}
+
+// get_logo_lines and choose_logo_size tests
+
+#[test]
+fn get_logo_lines_correct_input() {
+    assert_eq!(get_logo_lines("ubuntu", LogoSize::Small).len(), 6);
+    assert_eq!(get_logo_lines("ubuntu", LogoSize::Normal).len(), 11);
+    assert_eq!(get_logo_lines("ubuntu", LogoSize::Large).len(), 20);
+}
+
+#[test]
+fn get_logo_lines_edge_cases() {
+    // Distros without the size get their normal logo
+    let normal = get_logo_lines("kali", LogoSize::Normal);
+    assert_eq!(get_logo_lines("kali", LogoSize::Small), normal);
+    assert_eq!(get_logo_lines("kali", LogoSize::Large), normal);
+    assert_eq!(get_logo_lines("kali", LogoSize::Auto), normal);
+
+    assert!(get_logo_lines("unknown", LogoSize::Normal).is_empty());
+}
+
+#[test]
+fn choose_logo_size_correct_input() {
+    // Explicit sizes are kept as they are
+    assert_eq!(choose_logo_size("ubuntu", LogoSize::Large, 3, Some(5)), LogoSize::Large);
+
+    assert_eq!(choose_logo_size("ubuntu", LogoSize::Auto, 5, None), LogoSize::Small);
+    assert_eq!(choose_logo_size("ubuntu", LogoSize::Auto, 11, None), LogoSize::Normal);
+    assert_eq!(choose_logo_size("ubuntu", LogoSize::Auto, 18, None), LogoSize::Large);
+    assert_eq!(choose_logo_size("linux", LogoSize::Auto, 12, Some(50)), LogoSize::Large);
+}
+
+#[test]
+fn choose_logo_size_edge_cases() {
+    // Ubuntu is 6, 11 and 20 lines tall, the closest height wins
+    assert_eq!(choose_logo_size("ubuntu", LogoSize::Auto, 15, None), LogoSize::Normal);
+
+    // Sizes taller than the terminal are skipped
+    assert_eq!(choose_logo_size("ubuntu", LogoSize::Auto, 18, Some(15)), LogoSize::Normal);
+    assert_eq!(choose_logo_size("ubuntu", LogoSize::Auto, 18, Some(10)), LogoSize::Small);
+
+    // Nothing fits, so the smallest logo the distro ships is used
+    assert_eq!(choose_logo_size("ubuntu", LogoSize::Auto, 18, Some(3)), LogoSize::Small);
+    assert_eq!(choose_logo_size("linux", LogoSize::Auto, 18, Some(3)), LogoSize::Normal);
+
+    // Distros with a single size always get it
+    assert_eq!(choose_logo_size("kali", LogoSize::Auto, 40, None), LogoSize::Normal);
+}
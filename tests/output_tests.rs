@@ -0,0 +1,661 @@
+//! Test the machine readable output formats inside src/output
+
+use rustfetch::{
+    cli::ExportFormat,
+    common::{PercentageLevel, Thresholds},
+    config::{CheckConfig, CheckThresholds, ThresholdLevelConfig, ThresholdsConfig},
+    output::*,
+    report::*,
+};
+
+fn get_sample_report() -> SystemReport {
+    SystemReport {
+        cpu: Some(CpuInfo {
+            name: String::from("AMD Ryzen 5 5600X"),
+            frequency_mhz: None,
+        }),
+        ram: Some(MemoryInfo {
+            total_bytes: 16_000_000_000,
+            used_bytes: 4_000_000_000,
+            percent: 25,
+        }),
+        ..Default::default()
+    }
+}
+
+// shell_quote tests
+
+#[test]
+fn shell_quote_correct_input() {
+    let result = shell_quote("AMD Ryzen 5 5600X");
+    assert_eq!(result, String::from("'AMD Ryzen 5 5600X'"));
+
+    // Expansions must be disabled
+    let result = shell_quote("$(reboot) `id` $HOME");
+    assert_eq!(result, String::from("'$(reboot) `id` $HOME'"));
+}
+
+#[test]
+fn shell_quote_edge_cases() {
+    // Example used by the doc comment of shell_quote() in env.rs
+    let result = shell_quote("it's");
+    assert_eq!(result, String::from(r"'it'\''s'"));
+
+    let result = shell_quote("");
+    assert_eq!(result, String::from("''"));
+
+    let result = shell_quote("line\nbreak");
+    assert_eq!(result, String::from("'line\nbreak'"));
+}
+
+// env output tests
+
+#[test]
+fn get_variable_name_correct_input() {
+    let result = get_variable_name("ram.used_bytes");
+    assert_eq!(result, String::from("RUSTFETCH_RAM_USED_BYTES"));
+
+    let result = get_variable_name("screen.resolution.width");
+    assert_eq!(result, String::from("RUSTFETCH_SCREEN_RESOLUTION_WIDTH"));
+}
+
+#[test]
+fn get_env_lines_skips_unavailable_values() {
+    let result = get_env_lines(&get_sample_report());
+    assert_eq!(result, vec![
+        String::from("RUSTFETCH_CPU_NAME='AMD Ryzen 5 5600X'"),
+        String::from("RUSTFETCH_RAM_TOTAL_BYTES=16000000000"),
+        String::from("RUSTFETCH_RAM_USED_BYTES=4000000000"),
+        String::from("RUSTFETCH_RAM_PERCENT=25"),
+    ]);
+
+    let result = get_env_lines(&SystemReport::default());
+    assert!(result.is_empty());
+}
+
+// prometheus output tests
+
+#[test]
+fn escape_label_value_correct_input() {
+    // Example used by the doc comment of escape_label_value() in prometheus.rs
+    let result = escape_label_value(r#"Intel "Core""#);
+    assert_eq!(result, String::from(r#"Intel \"Core\""#));
+
+    let result = escape_label_value("AMD Ryzen 5 5600X");
+    assert_eq!(result, String::from("AMD Ryzen 5 5600X"));
+}
+
+#[test]
+fn escape_label_value_edge_cases() {
+    let result = escape_label_value("C:\\path\nnext");
+    assert_eq!(result, String::from(r"C:\\path\nnext"));
+
+    let result = escape_label_value("");
+    assert_eq!(result, String::from(""));
+}
+
+#[test]
+fn get_prometheus_metrics_correct_input() {
+    let result = get_prometheus_metrics(&get_sample_report());
+    assert_eq!(
+        result,
+        "# HELP rustfetch_info Static system information as labels\n# TYPE rustfetch_info \
+         gauge\nrustfetch_info{cpu=\"AMD Ryzen 5 5600X\"} 1\n# HELP rustfetch_ram_total_bytes \
+         Total RAM in bytes\n# TYPE rustfetch_ram_total_bytes gauge\nrustfetch_ram_total_bytes \
+         16000000000\n# HELP rustfetch_ram_used_bytes Used RAM in bytes\n# TYPE \
+         rustfetch_ram_used_bytes gauge\nrustfetch_ram_used_bytes 4000000000\n"
+    );
+}
+
+#[test]
+fn get_prometheus_metrics_empty_report() {
+    // rustfetch_info is always exported, even without labels
+    let result = get_prometheus_metrics(&SystemReport::default());
+    assert!(result.ends_with("rustfetch_info 1\n"));
+}
+
+#[test]
+fn get_prometheus_metrics_battery() {
+    let mut report = SystemReport {
+        battery: Some(BatteryInfo {
+            capacity_percent: 85,
+            status: String::from("Charging"),
+        }),
+        ..Default::default()
+    };
+    let result = get_prometheus_metrics(&report);
+    assert!(result.contains("\nrustfetch_battery_capacity_percent 85\n"));
+    assert!(result.contains("\nrustfetch_battery_charging 1\n"));
+
+    // Unplugging keeps the same series
+    if let Some(battery) = report.battery.as_mut() {
+        battery.status = String::from("Discharging");
+    }
+    let result = get_prometheus_metrics(&report);
+    assert!(result.contains("\nrustfetch_battery_capacity_percent 85\n"));
+    assert!(result.contains("\nrustfetch_battery_charging 0\n"));
+}
+
+// status bar output tests
+
+#[test]
+fn get_bar_blocks_correct_input() {
+    let result = get_bar_blocks(&get_sample_report(), &Thresholds::default());
+    assert_eq!(result.len(), 2);
+
+    assert_eq!(result[0].name, "cpu");
+    assert_eq!(result[0].level, None);
+
+    assert_eq!(result[1].name, "ram");
+    assert_eq!(result[1].text, String::from("RAM 25%"));
+    assert_eq!(result[1].level, Some(PercentageLevel::Ok));
+    assert_eq!(result[1].percentage, Some(25));
+}
+
+#[test]
+fn get_bar_blocks_custom_thresholds() -> Result<(), String> {
+    // The sample RAM is at 25%
+    let config = ThresholdsConfig {
+        ram: Some(vec![
+            ThresholdLevelConfig {
+                at: 0,
+                color: String::from("green"),
+            },
+            ThresholdLevelConfig {
+                at: 20,
+                color: String::from("yellow"),
+            },
+            ThresholdLevelConfig {
+                at: 90,
+                color: String::from("red"),
+            },
+        ]),
+        ..ThresholdsConfig::default()
+    };
+    let result = get_bar_blocks(&get_sample_report(), &Thresholds::new(&config)?);
+    assert_eq!(result[1].level, Some(PercentageLevel::Warn));
+
+    Ok(())
+}
+
+#[test]
+fn get_waybar_line_correct_input() -> Result<(), serde_json::Error> {
+    let blocks = get_bar_blocks(&get_sample_report(), &Thresholds::default());
+    let result: serde_json::Value = serde_json::from_str(&get_waybar_line(&blocks)?)?;
+
+    assert_eq!(result["text"], "AMD Ryzen 5 5600X | RAM 25%");
+    assert_eq!(result["class"], "ok");
+    assert_eq!(result["percentage"], 25);
+
+    Ok(())
+}
+
+#[test]
+fn get_waybar_line_edge_cases() -> Result<(), serde_json::Error> {
+    // Without percentages there is no class nor percentage at all
+    let result = get_waybar_line(&[])?;
+    assert_eq!(result, String::from(r#"{"text":"","tooltip":""}"#));
+
+    Ok(())
+}
+
+#[test]
+fn get_i3bar_line_correct_input() -> Result<(), serde_json::Error> {
+    let blocks = get_bar_blocks(&get_sample_report(), &Thresholds::default());
+    let result = get_i3bar_line(&blocks)?;
+    assert_eq!(
+        result,
+        String::from(
+            r##"[{"name":"cpu","full_text":"AMD Ryzen 5 5600X"},{"name":"ram","full_text":"RAM 25%","color":"#00FF00"}]"##
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn get_status_line_i3bar_separators() -> Result<(), serde_json::Error> {
+    let blocks = get_bar_blocks(&get_sample_report(), &Thresholds::default());
+    let first = get_status_line(BarProtocol::I3bar, &blocks, true)?;
+    let second = get_status_line(BarProtocol::I3bar, &blocks, false)?;
+    assert!(first.starts_with('['));
+    assert_eq!(second, format!(",{}", first));
+
+    // The lines must form a valid JSON array without a trailing comma
+    let stream = format!("[\n{}\n{}\n{}\n]", first, second, second);
+    let result: Vec<serde_json::Value> = serde_json::from_str(&stream)?;
+    assert_eq!(result.len(), 3);
+
+    // Only i3bar lines are separated
+    let result = get_status_line(BarProtocol::Polybar, &blocks, false)?;
+    assert_eq!(result, get_polybar_line(&blocks));
+
+    Ok(())
+}
+
+#[test]
+fn get_polybar_line_correct_input() {
+    let blocks = get_bar_blocks(&get_sample_report(), &Thresholds::default());
+    let result = get_polybar_line(&blocks);
+    assert_eq!(result, String::from("AMD Ryzen 5 5600X | %{F#00FF00}RAM 25%%%{F-}"));
+}
+
+#[test]
+fn get_polybar_line_edge_cases() {
+    // Values must never be read as polybar tags
+    let blocks = vec![BarBlock {
+        name: "gpu",
+        text: String::from("%{F#FF0000}GPU"),
+        tooltip: String::new(),
+        level: None,
+        percentage: None,
+    }];
+    let result = get_polybar_line(&blocks);
+    assert_eq!(result, String::from("%%{F#FF0000}GPU"));
+}
+
+// export tests
+
+#[test]
+fn parse_ansi_line_correct_input() {
+    let result = parse_ansi_line("\x1b[1mCPU:\x1b[0m Ryzen (\x1b[38;2;255;156;0m25%\x1b[0m)");
+    assert_eq!(result, vec![
+        StyledSpan {
+            text: String::from("CPU:"),
+            style: SpanStyle {
+                color: None,
+                background: None,
+                bold: true,
+            },
+        },
+        StyledSpan {
+            text: String::from(" Ryzen ("),
+            style: SpanStyle::default(),
+        },
+        StyledSpan {
+            text: String::from("25%"),
+            style: SpanStyle {
+                color: Some((255, 156, 0)),
+                background: None,
+                bold: false,
+            },
+        },
+        StyledSpan {
+            text: String::from(")"),
+            style: SpanStyle::default(),
+        },
+    ]);
+}
+
+#[test]
+fn parse_ansi_line_edge_cases() {
+    // 256 colors are converted through the xterm color cube
+    let result = parse_ansi_line("\x1b[38;5;196mred");
+    assert_eq!(result[0].style.color, Some((255, 0, 0)));
+
+    // Non-SGR sequences are dropped, as are incomplete truecolor sequences
+    let result = parse_ansi_line("\x1b[2Kplain\x1b[38;2;1m");
+    assert_eq!(result, vec![StyledSpan {
+        text: String::from("plain"),
+        style: SpanStyle::default(),
+    }]);
+
+    // Out of range colors are ignored instead of wrapping around, the rest still applies
+    let result = parse_ansi_line("\x1b[38;2;300;0;0;1mbold\x1b[48;5;256mbold");
+    assert_eq!(result, vec![StyledSpan {
+        text: String::from("boldbold"),
+        style: SpanStyle {
+            bold: true,
+            ..SpanStyle::default()
+        },
+    }]);
+
+    let result = parse_ansi_line("");
+    assert!(result.is_empty());
+}
+
+#[test]
+fn parse_ansi_line_backgrounds() {
+    // Swatches of the colors module, E.g: "   ".on_color(Color::Red)
+    let result = parse_ansi_line("\x1b[41m   \x1b[0m\x1b[101m   \x1b[49m");
+    assert_eq!(result[0].style.background, Some((205, 49, 49)));
+    assert_eq!(result[1].style.background, Some((241, 76, 76)));
+
+    // Half-blocks of image logos have a truecolor foreground and background
+    let result = parse_ansi_line("\x1b[38;2;1;2;3;48;2;4;5;6m▀\x1b[48;5;196m▀");
+    assert_eq!(result, vec![
+        StyledSpan {
+            text: String::from("▀"),
+            style: SpanStyle {
+                color: Some((1, 2, 3)),
+                background: Some((4, 5, 6)),
+                bold: false,
+            },
+        },
+        StyledSpan {
+            text: String::from("▀"),
+            style: SpanStyle {
+                color: Some((1, 2, 3)),
+                background: Some((255, 0, 0)),
+                bold: false,
+            },
+        },
+    ]);
+}
+
+#[test]
+fn export_ansi_backgrounds() {
+    let ansi_text = "ab\x1b[42m   \x1b[0m";
+
+    let svg = export_ansi(ansi_text, ExportFormat::Svg);
+    assert!(svg.contains(r##"<rect x="32.8" y="16" width="25.2" height="18" fill="#0dbc79"/>"##));
+
+    let html = export_ansi(ansi_text, ExportFormat::Html);
+    assert!(html.contains(r#"<span style="background-color: #0dbc79">   </span>"#));
+}
+
+#[test]
+fn escape_xml_correct_input() {
+    let result = escape_xml(r#"<b>"Tom" & 'Jerry'</b>"#);
+    assert_eq!(result, String::from("&lt;b&gt;&quot;Tom&quot; &amp; &#39;Jerry&#39;&lt;/b&gt;"));
+}
+
+#[test]
+fn export_ansi_is_reproducible() {
+    let ansi_text = "\x1b[31m /\\ \x1b[0m  \x1b[1mOS:\x1b[0m Arch <Linux>\n\x1b[31m/__\\\x1b[0m";
+
+    let svg = export_ansi(ansi_text, ExportFormat::Svg);
+    assert_eq!(svg, export_ansi(ansi_text, ExportFormat::Svg));
+    assert!(svg.starts_with("<svg "));
+    assert!(svg.contains(r##"<tspan fill="#cd3131"> /\ </tspan>"##));
+    assert!(svg.contains("Arch &lt;Linux&gt;"));
+
+    let html = export_ansi(ansi_text, ExportFormat::Html);
+    assert!(html.contains(r#"<span style="font-weight: bold">OS:</span>"#));
+}
+
+// bug report tests
+
+#[test]
+fn format_cell_correct_input() {
+    // Example used by the doc comment of format_cell() in bugreport.rs
+    let result = format_cell("a|b");
+    assert_eq!(result, String::from(r"`a\|b`"));
+
+    let result = format_cell("AMD Radeon RX 580");
+    assert_eq!(result, String::from("`AMD Radeon RX 580`"));
+}
+
+#[test]
+fn format_cell_edge_cases() {
+    // Backticks would close the inline code and new lines would break the table
+    let result = format_cell("`id`\nnext");
+    assert_eq!(result, String::from("`'id' next`"));
+
+    let result = format_cell("");
+    assert_eq!(result, String::from("``"));
+}
+
+#[test]
+fn get_bug_report_markdown_correct_input() {
+    let bug_report = BugReport {
+        version: String::from("0.3.0"),
+        target: String::from("linux x86_64"),
+        distro_id: String::from("fedora"),
+        config_path: String::from("/home/user/.config/rustfetch/config.toml (found)"),
+        cache_path: String::from("/home/user/.cache/rustfetch/cache.toml (missing)"),
+        data_sources: vec![("pci.ids", String::from("/usr/share/hwdata/pci.ids"))],
+        report: get_sample_report(),
+    };
+    let result = get_bug_report_markdown(&bug_report);
+
+    assert!(result.contains("| Distro ID | `fedora` |"));
+    assert!(result.contains("| pci.ids | `/usr/share/hwdata/pci.ids` |"));
+    assert!(result.contains("| cpu.name | `AMD Ryzen 5 5600X` |"));
+    assert!(result.contains("| ram.used_bytes | `4000000000` |"));
+    assert!(result.contains("| gpu | Unavailable |"));
+    // Colors are never printed
+    assert!(!result.contains('\x1b'));
+}
+
+#[test]
+fn bug_report_writes_nothing() -> Result<(), Box<dyn std::error::Error>> {
+    let root = std::env::temp_dir().join(format!("rustfetch_bug_report_{}", std::process::id()));
+    let cache_dir = root.join("cache");
+    let config_dir = root.join("config");
+    std::fs::create_dir_all(&cache_dir)?;
+    std::fs::create_dir_all(&config_dir)?;
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_rustfetch"))
+        .arg("--bug-report")
+        .env("XDG_CACHE_HOME", &cache_dir)
+        .env("XDG_CONFIG_HOME", &config_dir)
+        .output()?;
+    let cache_entries = std::fs::read_dir(&cache_dir)?.count();
+    let config_entries = std::fs::read_dir(&config_dir)?.count();
+    std::fs::remove_dir_all(&root)?;
+
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)?.contains("| GPU name source |"));
+    assert_eq!(cache_entries, 0);
+    assert_eq!(config_entries, 0);
+    assert!(!String::from_utf8(output.stderr)?.contains("Created"));
+    Ok(())
+}
+
+// check tests
+
+#[test]
+fn check_usage_correct_input() {
+    let thresholds = CheckThresholds::new(80, 95);
+    assert_eq!(check_usage(25, &thresholds), CheckStatus::Ok);
+    assert_eq!(check_usage(81, &thresholds), CheckStatus::Warning);
+    assert_eq!(check_usage(96, &thresholds), CheckStatus::Critical);
+}
+
+#[test]
+fn check_capacity_correct_input() {
+    let thresholds = CheckThresholds::new(30, 10);
+    assert_eq!(check_capacity(75, &thresholds), CheckStatus::Ok);
+    assert_eq!(check_capacity(29, &thresholds), CheckStatus::Warning);
+    assert_eq!(check_capacity(5, &thresholds), CheckStatus::Critical);
+}
+
+#[test]
+fn run_checks_thresholds_boundaries() {
+    // Nagios only alerts once a value is past the range, so reaching a threshold is not enough
+    let mut report = get_sample_report();
+    report.ram = report.ram.map(|ram| MemoryInfo { percent: 80, ..ram });
+    let result = run_checks(&report, &CheckConfig::default());
+    assert_eq!(result.status, CheckStatus::Ok);
+    assert_eq!(result.line, "RUSTFETCH OK - RAM 80% | ram=80%;80;95;0;100");
+
+    report.ram = report.ram.map(|ram| MemoryInfo { percent: 95, ..ram });
+    let result = run_checks(&report, &CheckConfig::default());
+    assert_eq!(result.status, CheckStatus::Warning);
+    assert_eq!(result.line, "RUSTFETCH WARNING - RAM 95% | ram=95%;80;95;0;100");
+
+    let mut report = SystemReport {
+        battery: Some(BatteryInfo {
+            capacity_percent: 30,
+            status: String::from("Discharging"),
+        }),
+        ..Default::default()
+    };
+    let result = run_checks(&report, &CheckConfig::default());
+    assert_eq!(result.status, CheckStatus::Ok);
+    assert_eq!(result.line, "RUSTFETCH OK - Battery 30% | battery=30%;30:;10:;0;100");
+
+    report.battery = report.battery.map(|battery| BatteryInfo {
+        capacity_percent: 10,
+        ..battery
+    });
+    let result = run_checks(&report, &CheckConfig::default());
+    assert_eq!(result.status, CheckStatus::Warning);
+    assert_eq!(result.line, "RUSTFETCH WARNING - Battery 10% | battery=10%;30:;10:;0;100");
+}
+
+#[test]
+fn check_usage_edge_cases() {
+    // A missing threshold is never reached
+    let thresholds = CheckThresholds {
+        warn: None,
+        crit: Some(95),
+    };
+    assert_eq!(check_usage(90, &thresholds), CheckStatus::Ok);
+    assert_eq!(check_usage(100, &CheckThresholds::default()), CheckStatus::Ok);
+}
+
+#[test]
+fn run_checks_correct_input() {
+    let mut report = get_sample_report();
+    report.disk = Some(DiskInfo {
+        mount_point: String::from("/"),
+        total_bytes: 500_000_000_000,
+        used_bytes: 450_000_000_000,
+        percent: 90,
+    });
+
+    let result = run_checks(&report, &CheckConfig::default());
+    assert_eq!(result.status, CheckStatus::Warning);
+    assert_eq!(result.status.exit_code(), 1);
+    assert_eq!(
+        result.line,
+        "RUSTFETCH WARNING - RAM 25%, Disk 90% | ram=25%;80;95;0;100 disk=90%;80;95;0;100"
+    );
+}
+
+#[test]
+fn run_checks_capacity_perfdata() {
+    let report = SystemReport {
+        battery: Some(BatteryInfo {
+            capacity_percent: 64,
+            status: String::from("Discharging"),
+        }),
+        ..Default::default()
+    };
+
+    // Nagios reads a plain "30" as alerting above 30, battery alerts below it
+    let result = run_checks(&report, &CheckConfig::default());
+    assert_eq!(result.status, CheckStatus::Ok);
+    assert_eq!(result.line, "RUSTFETCH OK - Battery 64% | battery=64%;30:;10:;0;100");
+}
+
+#[test]
+fn format_threshold_correct_input() {
+    assert_eq!(format_threshold(Some(80), false), String::from("80"));
+    assert_eq!(format_threshold(Some(30), true), String::from("30:"));
+    assert_eq!(format_threshold(None, true), String::new());
+}
+
+#[test]
+fn run_checks_edge_cases() {
+    // Nothing to check is UNKNOWN, not OK
+    let result = run_checks(&SystemReport::default(), &CheckConfig::default());
+    assert_eq!(result.status, CheckStatus::Unknown);
+    assert_eq!(result.status.exit_code(), 3);
+
+    // Modules without thresholds are skipped
+    let check_config = CheckConfig {
+        ram: CheckThresholds::default(),
+        ..Default::default()
+    };
+    let result = run_checks(&get_sample_report(), &check_config);
+    assert_eq!(result.status, CheckStatus::Unknown);
+}
+
+#[test]
+fn print_check_error_correct_input() {
+    // Exit code 1 would be read as WARNING
+    assert_eq!(print_check_error(&"Unknown module: foo"), 3);
+}
+
+// template tests
+
+#[test]
+fn template_parse_correct_input() -> Result<(), String> {
+    let result = Template::parse("{cpu} | {ram.percent}%")?;
+    assert_eq!(result.parts, vec![
+        TemplatePart::Placeholder("cpu"),
+        TemplatePart::Text(String::from(" | ")),
+        TemplatePart::Placeholder("ram.percent"),
+        TemplatePart::Text(String::from("%")),
+    ]);
+
+    Ok(())
+}
+
+#[test]
+fn template_parse_edge_cases() -> Result<(), String> {
+    let result = Template::parse("{{literal}}")?;
+    assert_eq!(result.parts, vec![TemplatePart::Text(String::from("{literal}"))]);
+
+    let result = Template::parse("")?;
+    assert!(result.parts.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn template_parse_invalid_input() {
+    let result = Template::parse("{os} {cpu_usage}");
+    assert_eq!(result, Err(String::from("Unknown placeholder: {cpu_usage}")));
+
+    let result = Template::parse("{os");
+    assert_eq!(result, Err(String::from("Unclosed placeholder: {os")));
+
+    let result = Template::parse("os}");
+    assert_eq!(result, Err(String::from("Unmatched }, use }} for a literal brace")));
+}
+
+#[test]
+fn template_get_modules_correct_input() -> Result<(), String> {
+    let template = Template::parse("{ram.used}/{ram.total} {username} {cpu.frequency}")?;
+    assert_eq!(template.get_modules(), vec![
+        String::from("ram"),
+        String::from("identifier"),
+        String::from("cpu"),
+    ]);
+
+    let mut display = rustfetch::DisplayConfig::default();
+    template.enable_modules(&mut display)?;
+    assert!(display.ram && display.identifier && display.cpu);
+    assert!(!display.os && !display.gpu);
+    // Sub-options used by a placeholder are turned on
+    assert!(display.cpu_frequency);
+
+    Ok(())
+}
+
+#[test]
+fn template_render_correct_input() -> Result<(), String> {
+    let template = Template::parse("{cpu} | {ram.percent}% | {ram.total}")?;
+    let result = template.render(&get_sample_report());
+    assert_eq!(result, String::from("AMD Ryzen 5 5600X | 25% | 14.9 GB"));
+
+    // Unavailable values are left empty
+    let template = Template::parse("[{battery}]")?;
+    assert_eq!(template.render(&get_sample_report()), String::from("[]"));
+
+    Ok(())
+}
+
+#[test]
+fn template_render_cpu_frequency() -> Result<(), String> {
+    // Same units as the text output, MHz below 1 GHz
+    let mut report = get_sample_report();
+    let template = Template::parse("{cpu} | {cpu.frequency}")?;
+    report.cpu = report.cpu.map(|cpu| CpuInfo {
+        frequency_mhz: Some(800),
+        ..cpu
+    });
+    assert_eq!(template.render(&report), String::from("AMD Ryzen 5 5600X @ 800 MHz | 800 MHz"));
+
+    report.cpu = report.cpu.map(|cpu| CpuInfo {
+        frequency_mhz: Some(3700),
+        ..cpu
+    });
+    assert_eq!(template.render(&report), String::from("AMD Ryzen 5 5600X @ 3.7 GHz | 3.7 GHz"));
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+//! Test the JSON representation of the SystemReport, its keys are part of the --json output and
+//! must stay stable
+
+use rustfetch::report::*;
+
+#[test]
+fn test_unavailable_modules_are_null() -> Result<(), Box<dyn std::error::Error>> {
+    let report = SystemReport::default();
+    let json: serde_json::Value = serde_json::to_value(&report)?;
+
+    let keys = [
+        "identifier",
+        "os",
+        "kernel",
+        "cpu",
+        "gpu",
+        "screen",
+        "ram",
+        "swap",
+        "uptime",
+        "battery",
+        "power_draw",
+        "disk",
+    ];
+    for key in keys {
+        // Modules must never disappear from the document, they're null instead
+        assert_eq!(json.get(key), Some(&serde_json::Value::Null), "{} is not null", key);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_values_are_typed() -> Result<(), Box<dyn std::error::Error>> {
+    let report = SystemReport {
+        ram: Some(MemoryInfo {
+            total_bytes: 16_000_000_000,
+            used_bytes: 4_000_000_000,
+            percent: 25,
+        }),
+        power_draw: Some(PowerDrawInfo { watts: 12.5 }),
+        ..Default::default()
+    };
+    let json: serde_json::Value = serde_json::to_value(&report)?;
+
+    assert_eq!(json["ram"]["total_bytes"], serde_json::json!(16_000_000_000u64));
+    assert_eq!(json["ram"]["percent"], serde_json::json!(25));
+    assert_eq!(json["power_draw"]["watts"], serde_json::json!(12.5));
+
+    Ok(())
+}
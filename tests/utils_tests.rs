@@ -2,7 +2,10 @@
 
 use core::f64;
 
-use rustfetch::common::*;
+use rustfetch::{
+    common::*,
+    config::{ThresholdLevelConfig, ThresholdsConfig},
+};
 
 // extract_numeric_value tests
 #[test]
@@ -130,3 +133,336 @@ fn strip_cpu_name_empty_string() {
     let result = strip_cpu_name("");
     assert_eq!(result, String::from(""));
 }
+
+#[test]
+fn format_uptime_correct_input() {
+    // Example used by the doc comment of format_uptime() in utils.rs
+    let result = format_uptime(3725);
+    assert_eq!(result, String::from("01h 02m 05s"));
+
+    // Hours are omitted when they're 0
+    let result = format_uptime(59);
+    assert_eq!(result, String::from("00m 59s"));
+}
+
+#[test]
+fn format_uptime_edge_cases() {
+    let result = format_uptime(0);
+    assert_eq!(result, String::from("00m 00s"));
+
+    // Hours are not wrapped into days
+    let result = format_uptime(100 * 3600);
+    assert_eq!(result, String::from("100h 00m 00s"));
+}
+
+// PercentageLevel tests
+
+#[test]
+fn percentage_level_from_usage() {
+    assert_eq!(PercentageLevel::from_usage(0), PercentageLevel::Ok);
+    assert_eq!(PercentageLevel::from_usage(40), PercentageLevel::Warn);
+    assert_eq!(PercentageLevel::from_usage(79), PercentageLevel::Warn);
+    assert_eq!(PercentageLevel::from_usage(80), PercentageLevel::Critical);
+    // Percentages above 100 are still critical
+    assert_eq!(PercentageLevel::from_usage(2000), PercentageLevel::Critical);
+}
+
+#[test]
+fn percentage_level_from_capacity() {
+    assert_eq!(PercentageLevel::from_capacity(100.0), PercentageLevel::Ok);
+    assert_eq!(PercentageLevel::from_capacity(69.9), PercentageLevel::Warn);
+    assert_eq!(PercentageLevel::from_capacity(29.0), PercentageLevel::Critical);
+}
+
+// PercentageThresholds tests
+
+fn get_level_configs(levels: &[(u64, &str)]) -> Vec<ThresholdLevelConfig> {
+    levels
+        .iter()
+        .map(|(at, color)| ThresholdLevelConfig {
+            at: *at,
+            color: color.to_string(),
+        })
+        .collect()
+}
+
+#[test]
+fn percentage_thresholds_correct_input() -> Result<(), String> {
+    // Build servers sit at 85% of RAM by design
+    let config = get_level_configs(&[(0, "blue"), (90, "magenta"), (98, "#ff0000")]);
+    let thresholds = PercentageThresholds::usage().with_config("ram", Some(&config))?;
+    assert_eq!(thresholds.get_level(85.0), PercentageLevel::Ok);
+    assert_eq!(thresholds.get_level(90.0), PercentageLevel::Warn);
+    assert_eq!(thresholds.get_level(98.0), PercentageLevel::Critical);
+    assert_eq!(thresholds.get_color(85.0), colored::Color::Blue);
+    assert_eq!(thresholds.get_color(99.0), colored::Color::TrueColor { r: 255, g: 0, b: 0 });
+
+    // Any amount of levels, everything between the best and the worst one is a warning
+    let config = get_level_configs(&[
+        (0, "green"),
+        (50, "bright green"),
+        (70, "yellow"),
+        (85, "#ff8800"),
+        (95, "red"),
+    ]);
+    let thresholds = PercentageThresholds::usage().with_config("disk", Some(&config))?;
+    assert_eq!(thresholds.get_color(60.0), colored::Color::BrightGreen);
+    assert_eq!(thresholds.get_level(60.0), PercentageLevel::Warn);
+    assert_eq!(thresholds.get_level(90.0), PercentageLevel::Warn);
+    assert_eq!(thresholds.get_level(95.0), PercentageLevel::Critical);
+
+    // Battery levels still go up, but the first one is the critical one
+    let config = get_level_configs(&[(0, "red"), (10, "yellow"), (50, "green")]);
+    let thresholds = PercentageThresholds::capacity().with_config("battery", Some(&config))?;
+    assert_eq!(thresholds.get_level(60.0), PercentageLevel::Ok);
+    assert_eq!(thresholds.get_level(49.0), PercentageLevel::Warn);
+    assert_eq!(thresholds.get_level(9.5), PercentageLevel::Critical);
+    assert_eq!(thresholds.get_color(49.0), colored::Color::Yellow);
+
+    Ok(())
+}
+
+#[test]
+fn percentage_thresholds_edge_cases() -> Result<(), String> {
+    // The built-in thresholds match PercentageLevel
+    for percentage in 0 ..= 120 {
+        let usage = PercentageThresholds::usage().get_level(percentage as f64);
+        assert_eq!(usage, PercentageLevel::from_usage(percentage));
+        let capacity = PercentageThresholds::capacity().get_level(percentage as f64);
+        assert_eq!(capacity, PercentageLevel::from_capacity(percentage as f64));
+    }
+
+    // Two levels skip the warning and a single level is always ok
+    let config = get_level_configs(&[(0, "green"), (90, "red")]);
+    let thresholds = PercentageThresholds::usage().with_config("disk", Some(&config))?;
+    assert_eq!(thresholds.get_level(89.0), PercentageLevel::Ok);
+    assert_eq!(thresholds.get_level(90.0), PercentageLevel::Critical);
+    let config = get_level_configs(&[(0, "cyan")]);
+    let thresholds = PercentageThresholds::usage().with_config("swap", Some(&config))?;
+    assert_eq!(thresholds.get_level(100.0), PercentageLevel::Ok);
+
+    // Percentages below the first level belong to it
+    let config = get_level_configs(&[(20, "blue"), (60, "red")]);
+    let thresholds = PercentageThresholds::usage().with_config("ram", Some(&config))?;
+    assert_eq!(thresholds.get_color(5.0), colored::Color::Blue);
+
+    let thresholds = PercentageThresholds::usage().with_config("ram", None)?;
+    assert_eq!(thresholds, PercentageThresholds::usage());
+    let thresholds = Thresholds::new(&ThresholdsConfig::default())?;
+    assert_eq!(thresholds, Thresholds::default());
+
+    Ok(())
+}
+
+#[test]
+fn percentage_thresholds_invalid_input() {
+    let config = get_level_configs(&[(0, "green"), (80, "red"), (40, "yellow")]);
+    let result = PercentageThresholds::usage().with_config("ram", Some(&config));
+    assert_eq!(result, Err(String::from("Thresholds of ram must go up: 80 then 40")));
+
+    let config = get_level_configs(&[(30, "red"), (30, "yellow")]);
+    let result = PercentageThresholds::capacity().with_config("battery", Some(&config));
+    assert_eq!(result, Err(String::from("Thresholds of battery must go up: 30 then 30")));
+
+    let result = PercentageThresholds::usage().with_config("disk", Some(&[]));
+    assert_eq!(result, Err(String::from("Thresholds of disk need at least one level")));
+
+    let config = ThresholdsConfig {
+        swap: Some(get_level_configs(&[(0, "green"), (50, "blurple")])),
+        ..ThresholdsConfig::default()
+    };
+    assert_eq!(Thresholds::new(&config), Err(String::from("Unknown color: blurple")));
+}
+
+// parse_color tests
+
+#[test]
+fn parse_color_correct_input() {
+    assert_eq!(parse_color("blue"), Ok(colored::Color::Blue));
+    assert_eq!(parse_color("bright red"), Ok(colored::Color::BrightRed));
+    assert_eq!(parse_color("#ff8800"), Ok(colored::Color::TrueColor { r: 255, g: 136, b: 0 }));
+}
+
+#[test]
+fn parse_color_invalid_input() {
+    assert_eq!(parse_color("blurple"), Err(String::from("Unknown color: blurple")));
+    assert!(parse_color("#ff88").is_err());
+    assert!(parse_color("").is_err());
+}
+
+// write_file_atomically tests
+
+#[test]
+fn write_file_atomically_correct_input() -> std::io::Result<()> {
+    let path = std::env::temp_dir().join(format!("rustfetch_motd_test_{}", std::process::id()));
+
+    let result = write_file_atomically(&path, b"first");
+    assert!(result?);
+    assert_eq!(std::fs::read(&path)?, b"first");
+
+    // Writing the same contents twice must not touch the file
+    let result = write_file_atomically(&path, b"first");
+    assert!(!result?);
+
+    let result = write_file_atomically(&path, b"second");
+    assert!(result?);
+    assert_eq!(std::fs::read(&path)?, b"second");
+
+    std::fs::remove_file(&path)
+}
+
+#[cfg(unix)]
+#[test]
+fn write_file_atomically_keeps_permissions() -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = std::env::temp_dir().join(format!("rustfetch_mode_test_{}", std::process::id()));
+    std::fs::write(&path, b"first")?;
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o640))?;
+
+    assert!(write_file_atomically(&path, b"second")?);
+    assert_eq!(std::fs::read(&path)?, b"second");
+    assert_eq!(std::fs::metadata(&path)?.permissions().mode() & 0o777, 0o640);
+
+    std::fs::remove_file(&path)
+}
+
+#[cfg(unix)]
+#[test]
+fn write_file_atomically_follows_symlinks() -> std::io::Result<()> {
+    let directory =
+        std::env::temp_dir().join(format!("rustfetch_link_test_{}", std::process::id()));
+    std::fs::create_dir_all(&directory)?;
+    let target = directory.join("motd.dynamic");
+    let link = directory.join("motd");
+    std::fs::write(&target, b"first")?;
+    std::os::unix::fs::symlink(&target, &link)?;
+
+    assert!(write_file_atomically(&link, b"second")?);
+    assert!(std::fs::symlink_metadata(&link)?.file_type().is_symlink());
+    assert_eq!(std::fs::read(&target)?, b"second");
+
+    // A link whose target does not exist yet still stays a link
+    std::fs::remove_file(&target)?;
+    assert!(write_file_atomically(&link, b"third")?);
+    assert!(std::fs::symlink_metadata(&link)?.file_type().is_symlink());
+    assert_eq!(std::fs::read(&target)?, b"third");
+
+    std::fs::remove_dir_all(&directory)
+}
+
+#[test]
+fn write_file_atomically_invalid_input() {
+    let path = std::path::Path::new("/nonexistent_rustfetch_dir/motd");
+    let result = write_file_atomically(path, b"contents");
+    assert!(result.is_err());
+}
+
+// strip_ansi tests
+
+#[test]
+fn strip_ansi_correct_input() {
+    let result = strip_ansi("\x1b[1mOS:\x1b[0m \x1b[38;2;255;156;0mUbuntu\x1b[0m");
+    assert_eq!(result, String::from("OS: Ubuntu"));
+
+    // Hyperlinks are OSC sequences
+    let result = strip_ansi("\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x07");
+    assert_eq!(result, String::from("link"));
+}
+
+#[test]
+fn strip_ansi_edge_cases() {
+    assert_eq!(strip_ansi(""), String::new());
+    // An unfinished sequence is dropped as well
+    assert_eq!(strip_ansi("text\x1b[38;2"), String::from("text"));
+}
+
+// get_display_width tests
+
+#[test]
+fn get_display_width_correct_input() {
+    assert_eq!(get_display_width("Fedora"), 6);
+    // Example used by the doc comment of get_display_width() in utils.rs
+    assert_eq!(get_display_width("\x1b[1mOS:\x1b[0m 日本"), 8);
+}
+
+#[test]
+fn get_display_width_edge_cases() {
+    // Box-drawing and braille characters are multibyte but a single column wide
+    assert_eq!(get_display_width("╭──╮"), 4);
+    assert_eq!(get_display_width("⣿⣿"), 2);
+    assert_eq!(get_display_width(""), 0);
+}
+
+// truncate_to_width tests
+
+#[test]
+fn truncate_to_width_correct_input() {
+    // Example used by the doc comment of truncate_to_width() in utils.rs
+    assert_eq!(truncate_to_width("Intel Core i7", 8), String::from("Intel C…"));
+    assert_eq!(truncate_to_width("Fits", 4), String::from("Fits"));
+}
+
+#[test]
+fn truncate_to_width_edge_cases() {
+    // Colors are kept and closed after the ellipsis
+    let result = truncate_to_width("\x1b[1mCPU:\x1b[0m Ryzen", 7);
+    assert_eq!(result, String::from("\x1b[1mCPU:\x1b[0m R…\x1b[0m"));
+    assert_eq!(get_display_width(&result), 7);
+
+    // A wide character that does not fit is not split in half
+    assert_eq!(truncate_to_width("日本語", 4), String::from("日…"));
+
+    assert_eq!(truncate_to_width("text", 0), String::new());
+}
+
+// wrap_to_width tests
+
+#[test]
+fn wrap_to_width_correct_input() {
+    let result = wrap_to_width("CPU: AMD Ryzen 5 5600X", 14, 5);
+    assert_eq!(result, vec![
+        String::from("CPU: AMD Ryzen"),
+        String::from("     5 5600X"),
+    ]);
+
+    assert_eq!(wrap_to_width("Fits", 4, 0), vec![String::from("Fits")]);
+}
+
+#[test]
+fn wrap_to_width_edge_cases() {
+    // Words longer than the line are split
+    let result = wrap_to_width("abcdefgh", 3, 0);
+    assert_eq!(result, vec![String::from("abc"), String::from("def"), String::from("gh")]);
+
+    // Colors are closed at the end of a line and reopened on the next one
+    let result = wrap_to_width("\x1b[31mred text\x1b[0m", 4, 0);
+    assert_eq!(result, vec![
+        String::from("\x1b[31mred\x1b[0m"),
+        String::from("\x1b[31mtext\x1b[0m"),
+    ]);
+
+    // An indent wider than the line is ignored instead of looping forever
+    let result = wrap_to_width("abcd", 2, 5);
+    assert_eq!(result, vec![String::from("ab"), String::from("cd")]);
+}
+
+// get_os_release_value tests
+
+#[test]
+fn get_os_release_value_correct_input() {
+    let content = "NAME=\"Kubuntu\"\nID=kubuntu\nID_LIKE=\"ubuntu debian\"\n";
+    assert_eq!(get_os_release_value(content, "ID"), Some(String::from("kubuntu")));
+    // Example used by the doc comment of get_os_release_value() in utils.rs
+    assert_eq!(get_os_release_value(content, "ID_LIKE"), Some(String::from("ubuntu debian")));
+}
+
+#[test]
+fn get_os_release_value_edge_cases() {
+    // ID must not match ID_LIKE or VERSION_ID
+    let content = "ID_LIKE=arch\nVERSION_ID=2024\nID='artix'\n";
+    assert_eq!(get_os_release_value(content, "ID"), Some(String::from("artix")));
+
+    assert_eq!(get_os_release_value("ID=\"\"", "ID"), None);
+    assert_eq!(get_os_release_value("", "ID_LIKE"), None);
+}